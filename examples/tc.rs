@@ -1,21 +1,23 @@
 use chrono::{DateTime, NaiveDate, Utc};
 use kingtime::daily_workings::timerecord::{Code, Request, TimeRecord};
+use kingtime::{EmployeeCode, EmployeeKey};
 
 fn get_access_token() -> String {
     std::env::var("TC_KINGTIME_ACCESS_TOKEN")
         .expect("environment variable TC_KINGTIME_ACCESS_TOKEN is unset")
 }
 
-async fn get_employee_key() -> String {
-    let code = std::env::var("TC_EMPLOYEE_NUMBER")
-        .expect("environment variable TC_EMPLOYEE_NUMBER is unset");
+async fn get_employee_key() -> EmployeeKey {
+    let code: EmployeeCode = std::env::var("TC_EMPLOYEE_NUMBER")
+        .expect("environment variable TC_EMPLOYEE_NUMBER is unset")
+        .into();
     let token = get_access_token();
     let resp = kingtime::employees::get(&token, &code).await.unwrap();
     resp.key
 }
 
 fn today() -> NaiveDate {
-    Utc::today().naive_local()
+    Utc::now().date_naive()
 }
 
 fn now() -> DateTime<Utc> {
@@ -26,15 +28,13 @@ async fn get_my_timerecords(date: NaiveDate) -> Vec<TimeRecord> {
     let token = get_access_token();
     let key = get_employee_key().await;
 
-    let resp = kingtime::daily_workings::timerecord::get(&token, &[&key], date, date)
-        .await
-        .unwrap();
+    let mut dws =
+        kingtime::daily_workings::timerecord::get_by_date(&token, std::slice::from_ref(&key), date)
+            .await
+            .unwrap();
 
-    let mut dws = resp.0;
     assert_eq!(dws.len(), 1);
-    let mut dw = dws.remove(0);
-    assert_eq!(dw.daily_workings.len(), 1);
-    let dw = dw.daily_workings.remove(0);
+    let dw = dws.remove(0);
     assert_eq!(dw.date, date);
     assert_eq!(dw.employee_key, key);
     let mut trs = dw.time_record;
@@ -46,13 +46,9 @@ async fn timecard(code: Code) {
     let token = get_access_token();
     let key = get_employee_key().await;
 
-    let req = Request {
-        date: today(),
-        time: now(),
-        code,
-    };
+    let req = Request::new(today(), now(), code);
     println!("{}", serde_json::to_string_pretty(&req).unwrap());
-    let _resp = kingtime::daily_workings::timerecord::post(&token, &key, &req)
+    kingtime::daily_workings::timerecord::post(&token, &key, &req)
         .await
         .unwrap();
 }
@@ -76,6 +72,9 @@ async fn main() {
                 | kingtime::daily_workings::timerecord::Code::BreakStart => {
                     println!("finished the work (or have a break)");
                 }
+                kingtime::daily_workings::timerecord::Code::Other(ref code) => {
+                    println!("unknown (last record has code {})", code);
+                }
             }
         }
         "in" => timecard(Code::In).await,
@@ -0,0 +1,744 @@
+//! Synchronous counterparts of the async API.
+//!
+//! Each function runs its async twin to completion on a current-thread
+//! runtime shared by the whole process, so behavior (retries, timeouts, error
+//! mapping) is identical. This is used instead of `reqwest::blocking` to keep
+//! a single implementation, and works with any transport. Like
+//! `reqwest::blocking`, these must not be called from within an async
+//! runtime.
+//!
+//! Like the async free functions, each call builds a new
+//! [`Client`](crate::Client). To reuse its connections across calls, build
+//! one and run its methods with [`block_on`]:
+//!
+//! ```no_run
+//! # fn run() -> kingtime::Result<()> {
+//! let client = kingtime::Client::new("token");
+//! let employee = kingtime::blocking::block_on(client.employee(&"1000".into()))?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Panics
+//!
+//! The first call panics if the runtime can't be started, e.g. when the
+//! process is out of file descriptors.
+
+use std::future::Future;
+use std::sync::OnceLock;
+
+pub use crate::{Error, ResponseEnvelope, Result, TokenInfo};
+
+/// Runs `future` to completion on the runtime of this module.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("failed to start the runtime")
+        })
+        .block_on(future)
+}
+
+pub fn validate_token(access_token: &str) -> Result<TokenInfo> {
+    block_on(crate::validate_token(access_token))
+}
+
+pub mod administrators {
+    use crate::Result;
+
+    pub use crate::administrators::Administrator;
+
+    pub fn list(access_token: &str) -> Result<Vec<Administrator>> {
+        super::block_on(crate::administrators::list(access_token))
+    }
+
+    pub fn get(access_token: &str, admin_key: &str) -> Result<Administrator> {
+        super::block_on(crate::administrators::get(access_token, admin_key))
+    }
+}
+
+pub mod companies {
+    use crate::Result;
+
+    pub use crate::companies::{Company, Settings};
+
+    pub fn get(access_token: &str) -> Result<Vec<Company>> {
+        super::block_on(crate::companies::get(access_token))
+    }
+}
+
+pub mod employees {
+    use crate::{ResponseEnvelope, Result};
+    use chrono::NaiveDate;
+
+    pub use crate::employees::{
+        AdditionalField, CreateRequest, CreateResponse, Employee, EmployeeCode, EmployeeGroup,
+        EmployeeKey, ListParams, NewKey, Response, UpdateRequest,
+    };
+
+    pub fn reissue_key(access_token: &str, current_key: &EmployeeKey) -> Result<NewKey> {
+        super::block_on(crate::employees::reissue_key(access_token, current_key))
+    }
+
+    pub fn update(access_token: &str, key: &EmployeeKey, req: &UpdateRequest) -> Result<Response> {
+        super::block_on(crate::employees::update(access_token, key, req))
+    }
+
+    pub fn create(access_token: &str, req: &CreateRequest) -> Result<CreateResponse> {
+        super::block_on(crate::employees::create(access_token, req))
+    }
+
+    pub fn list(access_token: &str, params: &ListParams) -> Result<Vec<Employee>> {
+        super::block_on(crate::employees::list(access_token, params))
+    }
+
+    pub fn get(access_token: &str, code: &EmployeeCode) -> Result<Response> {
+        super::block_on(crate::employees::get(access_token, code))
+    }
+
+    pub fn get_with(
+        access_token: &str,
+        code: &EmployeeCode,
+        fields: &[AdditionalField],
+    ) -> Result<Response> {
+        super::block_on(crate::employees::get_with(access_token, code, fields))
+    }
+
+    pub fn get_as_of(
+        access_token: &str,
+        code: &EmployeeCode,
+        date: Option<NaiveDate>,
+    ) -> Result<Response> {
+        super::block_on(crate::employees::get_as_of(access_token, code, date))
+    }
+
+    pub fn get_with_meta(
+        access_token: &str,
+        code: &EmployeeCode,
+    ) -> Result<ResponseEnvelope<Response>> {
+        super::block_on(crate::employees::get_with_meta(access_token, code))
+    }
+}
+
+pub mod divisions {
+    use crate::Result;
+
+    pub use crate::divisions::{CreateDivision, Division, DivisionCode, UpdateDivision};
+
+    pub fn list(access_token: &str) -> Result<Vec<Division>> {
+        super::block_on(crate::divisions::list(access_token))
+    }
+
+    pub fn create(access_token: &str, req: &CreateDivision) -> Result<()> {
+        super::block_on(crate::divisions::create(access_token, req))
+    }
+
+    pub fn update(access_token: &str, code: &str, req: &UpdateDivision) -> Result<()> {
+        super::block_on(crate::divisions::update(access_token, code, req))
+    }
+
+    pub fn delete(access_token: &str, code: &str) -> Result<()> {
+        super::block_on(crate::divisions::delete(access_token, code))
+    }
+}
+
+pub mod daily_workings {
+    use crate::Result;
+    use chrono::NaiveDate;
+
+    pub use crate::daily_workings::{
+        AdditionalField, CurrentDateEmployee, CustomDailyWorking, DailyWorking, DailyWorkings,
+        DivisionBasis, FulltimeHoliday, HalfdayHoliday, HolidaysObtained, HourHoliday, Params,
+        Query, Response,
+    };
+
+    pub fn get(access_token: &str) -> Result<Response> {
+        super::block_on(crate::daily_workings::get(access_token))
+    }
+
+    pub fn get_with(access_token: &str, query: &Query) -> Result<Response> {
+        super::block_on(crate::daily_workings::get_with(access_token, query))
+    }
+
+    pub fn get_by_date(
+        access_token: &str,
+        date: NaiveDate,
+        params: &Params,
+    ) -> Result<Vec<DailyWorking>> {
+        super::block_on(crate::daily_workings::get_by_date(
+            access_token,
+            date,
+            params,
+        ))
+    }
+
+    pub mod timerecord {
+        use crate::{EmployeeKey, ResponseEnvelope, Result};
+        use chrono::NaiveDate;
+
+        pub use crate::daily_workings::timerecord::{
+            AdditionalField, ChunkError, Code, DailyWorking, DailyWorkings, DeleteRequest, Filter,
+            ManyResponse, Request, Response, TimeRecord, KEYS_PER_REQUEST,
+        };
+
+        pub fn post(access_token: &str, key: &EmployeeKey, req: &Request) -> Result<()> {
+            super::super::block_on(crate::daily_workings::timerecord::post(
+                access_token,
+                key,
+                req,
+            ))
+        }
+
+        pub fn delete(access_token: &str, key: &EmployeeKey, req: &DeleteRequest) -> Result<()> {
+            super::super::block_on(crate::daily_workings::timerecord::delete(
+                access_token,
+                key,
+                req,
+            ))
+        }
+
+        pub fn get(
+            access_token: &str,
+            keys: &[EmployeeKey],
+            start: NaiveDate,
+            end: NaiveDate,
+        ) -> Result<Response> {
+            super::super::block_on(crate::daily_workings::timerecord::get(
+                access_token,
+                keys,
+                start,
+                end,
+            ))
+        }
+
+        pub fn get_all(
+            access_token: &str,
+            start: NaiveDate,
+            end: NaiveDate,
+            filter: &Filter,
+        ) -> Result<Response> {
+            super::super::block_on(crate::daily_workings::timerecord::get_all(
+                access_token,
+                start,
+                end,
+                filter,
+            ))
+        }
+
+        pub fn get_by_date(
+            access_token: &str,
+            keys: &[EmployeeKey],
+            date: NaiveDate,
+        ) -> Result<Vec<DailyWorking>> {
+            super::super::block_on(crate::daily_workings::timerecord::get_by_date(
+                access_token,
+                keys,
+                date,
+            ))
+        }
+
+        pub fn get_with(
+            access_token: &str,
+            keys: &[EmployeeKey],
+            start: NaiveDate,
+            end: NaiveDate,
+            filter: &Filter,
+        ) -> Result<Response> {
+            super::super::block_on(crate::daily_workings::timerecord::get_with(
+                access_token,
+                keys,
+                start,
+                end,
+                filter,
+            ))
+        }
+
+        pub fn get_with_meta(
+            access_token: &str,
+            keys: &[EmployeeKey],
+            start: NaiveDate,
+            end: NaiveDate,
+        ) -> Result<ResponseEnvelope<Response>> {
+            super::super::block_on(crate::daily_workings::timerecord::get_with_meta(
+                access_token,
+                keys,
+                start,
+                end,
+            ))
+        }
+
+        pub fn get_many(
+            access_token: &str,
+            keys: &[EmployeeKey],
+            start: NaiveDate,
+            end: NaiveDate,
+            concurrency: usize,
+        ) -> Result<ManyResponse> {
+            super::super::block_on(crate::daily_workings::timerecord::get_many(
+                access_token,
+                keys,
+                start,
+                end,
+                concurrency,
+            ))
+        }
+    }
+}
+
+pub mod daily_schedules {
+    use crate::{EmployeeKey, Result};
+    use chrono::NaiveDate;
+
+    pub use crate::daily_schedules::{
+        BreakPlan, BreakSchedule, DailySchedule, DailySchedules, Query, Response, ScheduleRequest,
+    };
+
+    pub fn get(access_token: &str, query: &Query) -> Result<Response> {
+        super::block_on(crate::daily_schedules::get(access_token, query))
+    }
+
+    pub fn get_by_date(
+        access_token: &str,
+        date: NaiveDate,
+        query: &Query,
+    ) -> Result<Vec<DailySchedule>> {
+        super::block_on(crate::daily_schedules::get_by_date(
+            access_token,
+            date,
+            query,
+        ))
+    }
+
+    pub fn post(
+        access_token: &str,
+        date: NaiveDate,
+        key: &EmployeeKey,
+        req: &ScheduleRequest,
+    ) -> Result<()> {
+        super::block_on(crate::daily_schedules::post(access_token, date, key, req))
+    }
+
+    pub fn update(
+        access_token: &str,
+        date: NaiveDate,
+        key: &EmployeeKey,
+        req: &ScheduleRequest,
+    ) -> Result<()> {
+        super::block_on(crate::daily_schedules::update(access_token, date, key, req))
+    }
+}
+
+pub mod monthly_workings {
+    use crate::{Result, YearMonth};
+
+    pub use crate::monthly_workings::{
+        AdditionalField, CustomMonthlyWorking, HolidayObtained, MonthlyWorking, Query,
+    };
+
+    pub fn get(access_token: &str, query: &Query) -> Result<Vec<MonthlyWorking>> {
+        super::block_on(crate::monthly_workings::get(access_token, query))
+    }
+
+    pub fn get_month(
+        access_token: &str,
+        month: YearMonth,
+        query: &Query,
+    ) -> Result<Vec<MonthlyWorking>> {
+        super::block_on(crate::monthly_workings::get_month(
+            access_token,
+            month,
+            query,
+        ))
+    }
+}
+
+pub mod monthly_schedules {
+    use crate::Result;
+
+    pub use crate::monthly_schedules::{MonthlySchedule, PlannedHoliday, Query};
+
+    pub fn get(access_token: &str, query: &Query) -> Result<Vec<MonthlySchedule>> {
+        super::block_on(crate::monthly_schedules::get(access_token, query))
+    }
+}
+
+pub mod requests {
+    pub use crate::requests::{ApprovalStep, Decision, Query, Status};
+
+    pub mod overtime {
+        use crate::requests::{Decision, Query};
+        use crate::Result;
+
+        pub use crate::requests::overtime::OvertimeRequest;
+
+        pub fn list(access_token: &str, query: &Query) -> Result<Vec<OvertimeRequest>> {
+            super::super::block_on(crate::requests::overtime::list(access_token, query))
+        }
+
+        pub fn decide(access_token: &str, request_key: &str, decision: Decision) -> Result<()> {
+            super::super::block_on(crate::requests::overtime::decide(
+                access_token,
+                request_key,
+                decision,
+            ))
+        }
+    }
+
+    pub mod schedule {
+        use crate::requests::{Decision, Query};
+        use crate::Result;
+
+        pub use crate::requests::schedule::{RequestedSchedule, ScheduleRequest};
+
+        pub fn list(access_token: &str, query: &Query) -> Result<Vec<ScheduleRequest>> {
+            super::super::block_on(crate::requests::schedule::list(access_token, query))
+        }
+
+        pub fn decide(access_token: &str, request_key: &str, decision: Decision) -> Result<()> {
+            super::super::block_on(crate::requests::schedule::decide(
+                access_token,
+                request_key,
+                decision,
+            ))
+        }
+    }
+
+    pub mod holiday {
+        use crate::requests::{Decision, Query};
+        use crate::Result;
+
+        pub use crate::requests::holiday::{HolidayRequest, HolidayUnit};
+
+        pub fn list(access_token: &str, query: &Query) -> Result<Vec<HolidayRequest>> {
+            super::super::block_on(crate::requests::holiday::list(access_token, query))
+        }
+
+        pub fn decide(access_token: &str, request_key: &str, decision: Decision) -> Result<()> {
+            super::super::block_on(crate::requests::holiday::decide(
+                access_token,
+                request_key,
+                decision,
+            ))
+        }
+    }
+}
+
+pub mod yearly_holidays {
+    use crate::employees::EmployeeKey;
+    use crate::Result;
+    use chrono::NaiveDate;
+
+    pub use crate::yearly_holidays::{Grant, Query, RemainingLeave, YearlyHolidays};
+
+    pub fn get(access_token: &str, key: &EmployeeKey, query: &Query) -> Result<YearlyHolidays> {
+        super::block_on(crate::yearly_holidays::get(access_token, key, query))
+    }
+
+    pub fn remaining_summary(
+        access_token: &str,
+        keys: &[EmployeeKey],
+        as_of: NaiveDate,
+    ) -> Result<Vec<(EmployeeKey, RemainingLeave)>> {
+        super::block_on(crate::yearly_holidays::remaining_summary(
+            access_token,
+            keys,
+            as_of,
+        ))
+    }
+}
+
+pub mod working_types {
+    use crate::Result;
+
+    pub use crate::working_types::WorkingType;
+
+    pub fn list(access_token: &str) -> Result<Vec<WorkingType>> {
+        super::block_on(crate::working_types::list(access_token))
+    }
+
+    pub fn get(access_token: &str, code: &str) -> Result<WorkingType> {
+        super::block_on(crate::working_types::get(access_token, code))
+    }
+}
+
+pub mod raw {
+    use crate::Result;
+    use http::Method;
+    use serde_json::Value;
+
+    pub fn get(access_token: &str, path: &str, query: &[(&str, &str)]) -> Result<Value> {
+        super::block_on(crate::raw::get(access_token, path, query))
+    }
+
+    pub fn send(
+        access_token: &str,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<&Value>,
+    ) -> Result<Value> {
+        super::block_on(crate::raw::send(access_token, method, path, query, body))
+    }
+}
+
+/// The `&str` signatures replaced by [`EmployeeKey`](crate::EmployeeKey) and
+/// [`EmployeeCode`](crate::EmployeeCode), see [`crate::compat`].
+#[allow(deprecated)]
+pub mod compat {
+    pub mod employees {
+        use crate::blocking::employees::{AdditionalField, NewKey, Response, UpdateRequest};
+        use crate::{ResponseEnvelope, Result};
+        use chrono::NaiveDate;
+
+        #[deprecated(note = "use `blocking::employees::get` with an `EmployeeCode`")]
+        pub fn get(access_token: &str, code: &str) -> Result<Response> {
+            super::super::block_on(crate::compat::employees::get(access_token, code))
+        }
+
+        #[deprecated(note = "use `blocking::employees::get_with` with an `EmployeeCode`")]
+        pub fn get_with(
+            access_token: &str,
+            code: &str,
+            fields: &[AdditionalField],
+        ) -> Result<Response> {
+            super::super::block_on(crate::compat::employees::get_with(
+                access_token,
+                code,
+                fields,
+            ))
+        }
+
+        #[deprecated(note = "use `blocking::employees::get_as_of` with an `EmployeeCode`")]
+        pub fn get_as_of(
+            access_token: &str,
+            code: &str,
+            date: Option<NaiveDate>,
+        ) -> Result<Response> {
+            super::super::block_on(crate::compat::employees::get_as_of(
+                access_token,
+                code,
+                date,
+            ))
+        }
+
+        #[deprecated(note = "use `blocking::employees::get_with_meta` with an `EmployeeCode`")]
+        pub fn get_with_meta(access_token: &str, code: &str) -> Result<ResponseEnvelope<Response>> {
+            super::super::block_on(crate::compat::employees::get_with_meta(access_token, code))
+        }
+
+        #[deprecated(note = "use `blocking::employees::update` with an `EmployeeKey`")]
+        pub fn update(access_token: &str, key: &str, req: &UpdateRequest) -> Result<Response> {
+            super::super::block_on(crate::compat::employees::update(access_token, key, req))
+        }
+
+        #[deprecated(note = "use `blocking::employees::reissue_key` with an `EmployeeKey`")]
+        pub fn reissue_key(access_token: &str, current_key: &str) -> Result<NewKey> {
+            super::super::block_on(crate::compat::employees::reissue_key(
+                access_token,
+                current_key,
+            ))
+        }
+    }
+
+    pub mod timerecord {
+        use crate::blocking::daily_workings::timerecord::{
+            DailyWorking, DeleteRequest, Filter, ManyResponse, Request, Response,
+        };
+        use crate::{ResponseEnvelope, Result};
+        use chrono::NaiveDate;
+
+        #[deprecated(
+            note = "use `blocking::daily_workings::timerecord::post` with an `EmployeeKey`"
+        )]
+        pub fn post(access_token: &str, key: &str, req: &Request) -> Result<()> {
+            super::super::block_on(crate::compat::timerecord::post(access_token, key, req))
+        }
+
+        #[deprecated(
+            note = "use `blocking::daily_workings::timerecord::delete` with an `EmployeeKey`"
+        )]
+        pub fn delete(access_token: &str, key: &str, req: &DeleteRequest) -> Result<()> {
+            super::super::block_on(crate::compat::timerecord::delete(access_token, key, req))
+        }
+
+        #[deprecated(note = "use `blocking::daily_workings::timerecord::get` with `EmployeeKey`s")]
+        pub fn get(
+            access_token: &str,
+            keys: &[&str],
+            start: NaiveDate,
+            end: NaiveDate,
+        ) -> Result<Response> {
+            super::super::block_on(crate::compat::timerecord::get(
+                access_token,
+                keys,
+                start,
+                end,
+            ))
+        }
+
+        #[deprecated(
+            note = "use `blocking::daily_workings::timerecord::get_with` with `EmployeeKey`s"
+        )]
+        pub fn get_with(
+            access_token: &str,
+            keys: &[&str],
+            start: NaiveDate,
+            end: NaiveDate,
+            filter: &Filter,
+        ) -> Result<Response> {
+            super::super::block_on(crate::compat::timerecord::get_with(
+                access_token,
+                keys,
+                start,
+                end,
+                filter,
+            ))
+        }
+
+        #[deprecated(
+            note = "use `blocking::daily_workings::timerecord::get_by_date` with `EmployeeKey`s"
+        )]
+        pub fn get_by_date(
+            access_token: &str,
+            keys: &[&str],
+            date: NaiveDate,
+        ) -> Result<Vec<DailyWorking>> {
+            super::super::block_on(crate::compat::timerecord::get_by_date(
+                access_token,
+                keys,
+                date,
+            ))
+        }
+
+        #[deprecated(
+            note = "use `blocking::daily_workings::timerecord::get_with_meta` with `EmployeeKey`s"
+        )]
+        pub fn get_with_meta(
+            access_token: &str,
+            keys: &[&str],
+            start: NaiveDate,
+            end: NaiveDate,
+        ) -> Result<ResponseEnvelope<Response>> {
+            super::super::block_on(crate::compat::timerecord::get_with_meta(
+                access_token,
+                keys,
+                start,
+                end,
+            ))
+        }
+
+        #[deprecated(
+            note = "use `blocking::daily_workings::timerecord::get_many` with `EmployeeKey`s"
+        )]
+        pub fn get_many(
+            access_token: &str,
+            keys: &[&str],
+            start: NaiveDate,
+            end: NaiveDate,
+            concurrency: usize,
+        ) -> Result<ManyResponse> {
+            super::super::block_on(crate::compat::timerecord::get_many(
+                access_token,
+                keys,
+                start,
+                end,
+                concurrency,
+            ))
+        }
+    }
+
+    pub mod daily_schedules {
+        use crate::blocking::daily_schedules::ScheduleRequest;
+        use crate::Result;
+        use chrono::NaiveDate;
+
+        #[deprecated(note = "use `blocking::daily_schedules::post` with an `EmployeeKey`")]
+        pub fn post(
+            access_token: &str,
+            date: NaiveDate,
+            key: &str,
+            req: &ScheduleRequest,
+        ) -> Result<()> {
+            super::super::block_on(crate::compat::daily_schedules::post(
+                access_token,
+                date,
+                key,
+                req,
+            ))
+        }
+
+        #[deprecated(note = "use `blocking::daily_schedules::update` with an `EmployeeKey`")]
+        pub fn update(
+            access_token: &str,
+            date: NaiveDate,
+            key: &str,
+            req: &ScheduleRequest,
+        ) -> Result<()> {
+            super::super::block_on(crate::compat::daily_schedules::update(
+                access_token,
+                date,
+                key,
+                req,
+            ))
+        }
+    }
+
+    pub mod yearly_holidays {
+        use crate::blocking::yearly_holidays::{Query, RemainingLeave, YearlyHolidays};
+        use crate::{EmployeeKey, Result};
+        use chrono::NaiveDate;
+
+        #[deprecated(note = "use `blocking::yearly_holidays::get` with an `EmployeeKey`")]
+        pub fn get(access_token: &str, key: &str, query: &Query) -> Result<YearlyHolidays> {
+            super::super::block_on(crate::compat::yearly_holidays::get(
+                access_token,
+                key,
+                query,
+            ))
+        }
+
+        #[deprecated(
+            note = "use `blocking::yearly_holidays::remaining_summary` with `EmployeeKey`s"
+        )]
+        pub fn remaining_summary(
+            access_token: &str,
+            keys: &[&str],
+            as_of: NaiveDate,
+        ) -> Result<Vec<(EmployeeKey, RemainingLeave)>> {
+            super::super::block_on(crate::compat::yearly_holidays::remaining_summary(
+                access_token,
+                keys,
+                as_of,
+            ))
+        }
+    }
+}
+
+#[test]
+fn blocking_call() {
+    use crate::mock::{MockServer, Response};
+    use crate::Client;
+
+    // the server lives on its own runtime, just like the real API
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let server = rt.block_on(MockServer::start(|_| {
+        Response::json(
+            200,
+            r#"{"lastName": "勤怠", "firstName": "太郎", "key": "8b6ee646"}"#,
+        )
+    }));
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .build()
+        .unwrap();
+
+    let resp = block_on(client.employee(&"1000".into())).unwrap();
+    assert_eq!(resp.key, "8b6ee646");
+    // the runtime, and with it the connection pool, outlives a call
+    let resp = block_on(client.employee(&"1000".into())).unwrap();
+    assert_eq!(resp.key, "8b6ee646");
+}
@@ -0,0 +1,455 @@
+//! Recording real API traffic for replaying it in tests, behind the
+//! `cassette` feature.
+//!
+//! A client set up with [`ClientBuilder::cassette`] writes each exchange to
+//! a JSON file in the given directory the first time it is made, and answers
+//! it from that file afterwards, without touching the network:
+//!
+//! ```no_run
+//! # async fn run() -> kingtime::Result<()> {
+//! use kingtime::cassette::Mode;
+//!
+//! let client = kingtime::Client::builder()
+//!     .access_token(std::env::var("KOT_TOKEN").unwrap_or_default())
+//!     .cassette("tests/cassettes", Mode::Auto)
+//!     .build()?;
+//! let employee = client.employee(&"1000".into()).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Requests are told apart by their method, path, query parameters in any
+//! order and body, so the same calls replay whatever the host or token. The
+//! access token is scrubbed from the files, and request headers are not
+//! recorded at all.
+//!
+//! [`ClientBuilder::cassette`]: crate::ClientBuilder::cassette
+
+use crate::token::SecretToken;
+use crate::transport::{BoxFuture, HttpRequest, HttpResponse, Transport};
+use crate::{Error, Result};
+use http::header::{self, HeaderMap, HeaderName, HeaderValue};
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Whether a [`Cassette`] sends requests or answers them from disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Replays what was recorded, and records what wasn't.
+    #[default]
+    Auto,
+    /// Only replays, failing with [`Error::Cassette`] on requests that were
+    /// never recorded. The one to use on CI.
+    Replay,
+    /// Sends every request, overwriting earlier recordings.
+    Record,
+}
+
+/// A [`Transport`] recording the exchanges of another one to a directory, or
+/// replaying them from it, depending on its [`Mode`].
+///
+/// Files are read and written synchronously, which is fine for tests but
+/// blocks the executor meanwhile.
+#[derive(Clone)]
+pub struct Cassette {
+    dir: PathBuf,
+    mode: Mode,
+    inner: Arc<dyn Transport>,
+}
+
+impl Cassette {
+    pub fn new(dir: impl Into<PathBuf>, mode: Mode, inner: impl Transport) -> Self {
+        Cassette::with_arc(dir.into(), mode, Arc::new(inner))
+    }
+
+    pub(crate) fn with_arc(dir: PathBuf, mode: Mode, inner: Arc<dyn Transport>) -> Self {
+        Cassette { dir, mode, inner }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    async fn send(&self, req: HttpRequest) -> Result<HttpResponse> {
+        let request = Recorded::request(&req);
+        let path = self.dir.join(request.file_name());
+        if self.mode != Mode::Record {
+            match fs::read(&path) {
+                Ok(file) => {
+                    let interaction: Interaction = serde_json::from_slice(&file).map_err(|e| {
+                        Error::Cassette(format!("invalid cassette {}: {}", path.display(), e))
+                    })?;
+                    return interaction.response.into_response();
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(Error::Cassette(format!(
+                        "failed to read {}: {}",
+                        path.display(),
+                        e
+                    )))
+                }
+            }
+            if self.mode == Mode::Replay {
+                return Err(Error::Cassette(format!(
+                    "no recording of {} {} in {}",
+                    request.method,
+                    request.target(),
+                    self.dir.display()
+                )));
+            }
+        }
+
+        let access_token = access_token(&req);
+        let resp = self.inner.send(req).await?;
+        let interaction = Interaction {
+            request,
+            response: RecordedResponse::new(&resp, access_token.as_ref()),
+        };
+        let json = serde_json::to_vec_pretty(&interaction).map_err(Error::Encode)?;
+        fs::create_dir_all(&self.dir)
+            .and_then(|()| fs::write(&path, json))
+            .map_err(|e| Error::Cassette(format!("failed to write {}: {}", path.display(), e)))?;
+        Ok(resp)
+    }
+}
+
+impl Transport for Cassette {
+    fn send(&self, req: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        Box::pin(Cassette::send(self, req))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Interaction {
+    request: Recorded,
+    response: RecordedResponse,
+}
+
+// What a request is matched by: the host, headers and token are left out.
+#[derive(Serialize, Deserialize)]
+struct Recorded {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    #[serde(flatten)]
+    body: Body,
+}
+
+impl Recorded {
+    fn request(req: &HttpRequest) -> Self {
+        let mut query: Vec<_> = req.url.query_pairs().into_owned().collect();
+        query.sort();
+        let body = match (&req.body, access_token(req)) {
+            (Some(body), Some(access_token)) => Body::new(access_token.redact(body).as_bytes()),
+            (Some(body), None) => Body::new(body),
+            (None, _) => Body::default(),
+        };
+        Recorded {
+            method: req.method.to_string(),
+            path: req.url.path().to_string(),
+            query,
+            body,
+        }
+    }
+
+    fn target(&self) -> String {
+        match self.query.is_empty() {
+            true => self.path.clone(),
+            false => {
+                let query = url::form_urlencoded::Serializer::new(String::new())
+                    .extend_pairs(&self.query)
+                    .finish();
+                format!("{}?{}", self.path, query)
+            }
+        }
+    }
+
+    // e.g. `GET-employees-1000-3f2a....json`, readable yet unique
+    fn file_name(&self) -> String {
+        let mut hash = Fnv::default();
+        hash.write(self.method.as_bytes());
+        hash.write(self.target().as_bytes());
+        hash.write(&self.body.bytes());
+        let path: String = self
+            .path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .skip(1) // the API version
+            .collect::<Vec<_>>()
+            .join("-")
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .take(64)
+            .collect();
+        format!("{}-{}-{:016x}.json", self.method, path, hash.0)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: BTreeMap<String, String>,
+    #[serde(flatten)]
+    body: Body,
+}
+
+impl RecordedResponse {
+    fn new(resp: &HttpResponse, access_token: Option<&SecretToken>) -> Self {
+        let headers = resp
+            .headers
+            .iter()
+            .filter(|(name, value)| **name != header::SET_COOKIE && !value.is_sensitive())
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = match access_token {
+            Some(access_token) => Body::new(access_token.redact(&resp.body).as_bytes()),
+            None => Body::new(&resp.body),
+        };
+        RecordedResponse {
+            status: resp.status.as_u16(),
+            headers,
+            body,
+        }
+    }
+
+    fn into_response(self) -> Result<HttpResponse> {
+        let invalid = |what: &str| Error::Cassette(format!("invalid {} in cassette", what));
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            headers.insert(
+                name.parse::<HeaderName>().map_err(|_| invalid("header"))?,
+                HeaderValue::from_str(value).map_err(|_| invalid("header"))?,
+            );
+        }
+        Ok(HttpResponse {
+            status: StatusCode::from_u16(self.status).map_err(|_| invalid("status"))?,
+            headers,
+            body: self.body.bytes(),
+        })
+    }
+}
+
+// JSON bodies are kept as JSON, so that recordings are easy to read and
+// edit; anything else as text.
+#[derive(Default, Serialize, Deserialize)]
+struct Body {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    json: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+}
+
+impl Body {
+    fn new(body: &[u8]) -> Self {
+        match serde_json::from_slice(body) {
+            Ok(json) => Body {
+                json: Some(json),
+                text: None,
+            },
+            Err(_) if body.is_empty() => Body::default(),
+            Err(_) => Body {
+                json: None,
+                text: Some(String::from_utf8_lossy(body).into_owned()),
+            },
+        }
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        match (&self.json, &self.text) {
+            (Some(json), _) => json.to_string().into_bytes(),
+            (None, Some(text)) => text.clone().into_bytes(),
+            (None, None) => vec![],
+        }
+    }
+}
+
+fn access_token(req: &HttpRequest) -> Option<SecretToken> {
+    req.headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .filter(|access_token| !access_token.is_empty())
+        .map(SecretToken::from)
+}
+
+// FNV-1a, whose output doesn't change between Rust releases unlike std's
+// hasher, so file names stay the same.
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes.iter().chain(&[0xff]) {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+#[cfg(test)]
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("kingtime-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn record_then_replay() {
+    use crate::mock::{json, FakeTransport};
+    use crate::Client;
+    use chrono::NaiveDate;
+
+    const TOKEN: &str = "s3cr3t-token";
+    let dir = scratch_dir("record-then-replay");
+    let live = FakeTransport::new(|req| match req.url.path() {
+        "/v1.0/employees/1000" => json(
+            200,
+            r#"{"code": "1000", "key": "8b6ee646", "lastName": "勤怠", "firstName": "太郎"}"#,
+        ),
+        "/v1.0/daily-workings/timerecord" => json(200, "[]"),
+        _ => json(
+            404,
+            r#"{"errors": [{"message": "s3cr3t-token: 対象が存在しません", "code": 302}]}"#,
+        ),
+    });
+    let client = Client::builder()
+        .access_token(TOKEN)
+        .transport(live.clone())
+        .cassette(&dir, Mode::Auto)
+        .build()
+        .unwrap();
+    let date = NaiveDate::from_ymd_opt(2016, 5, 1).unwrap();
+    assert_eq!(
+        client.employee(&"1000".into()).await.unwrap().key,
+        "8b6ee646"
+    );
+    client.employee(&"2000".into()).await.unwrap_err();
+    client
+        .timerecords(&["8b6ee646".into(), "a1b2c3".into()], date, date)
+        .await
+        .unwrap();
+    assert_eq!(live.requests().len(), 3);
+    // replayed from disk the second time
+    client.employee(&"1000".into()).await.unwrap();
+    assert_eq!(live.requests().len(), 3);
+
+    let mut files: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    files.sort();
+    assert_eq!(files.len(), 3);
+    for file in &files {
+        assert!(!fs::read_to_string(file).unwrap().contains(TOKEN));
+    }
+    let name = files[0].file_name().unwrap().to_str().unwrap();
+    assert!(
+        name.starts_with("GET-daily-workings-timerecord-"),
+        "{}",
+        name
+    );
+
+    // another token and host, and no network at all
+    let offline = FakeTransport::new(|req| panic!("sent {:?}", req));
+    let client = Client::builder()
+        .access_token("another-token")
+        .base_url("https://kot.example.com")
+        .transport(offline.clone())
+        .cassette(&dir, Mode::Replay)
+        .build()
+        .unwrap();
+    let employee = client.employee(&"1000".into()).await.unwrap();
+    assert_eq!(employee.last_name, "勤怠");
+    let err = client.employee(&"2000".into()).await.unwrap_err();
+    assert!(err.is_not_found());
+    assert!(err.to_string().contains("[REDACTED]"), "{}", err);
+    client
+        .timerecords(&["8b6ee646".into(), "a1b2c3".into()], date, date)
+        .await
+        .unwrap();
+    let err = client.employee(&"3000".into()).await.unwrap_err();
+    assert!(
+        matches!(err.inner(), Error::Cassette(msg) if msg.contains("GET /v1.0/employees/3000")),
+        "{}",
+        err
+    );
+    assert!(offline.requests().is_empty());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn match_requests() {
+    use http::Method;
+    use url::Url;
+
+    let request = |method: Method, url: &str, token: &str, body: Option<&str>| {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        Recorded::request(&HttpRequest {
+            method,
+            url: Url::parse(url).unwrap(),
+            headers,
+            body: body.map(|body| body.as_bytes().to_vec()),
+            timeout: None,
+        })
+        .file_name()
+    };
+    let get = |url| request(Method::GET, url, "token", None);
+    let post = |token, body| {
+        request(
+            Method::POST,
+            "https://api.kingtime.jp/v1.0/daily-workings/timerecord/8b6ee646",
+            token,
+            Some(body),
+        )
+    };
+
+    assert_eq!(
+        get("https://api.kingtime.jp/v1.0/daily-workings/timerecord?start=2016-05-01&end=2016-05-02"),
+        get("http://localhost:8080/v1.0/daily-workings/timerecord?end=2016-05-02&start=2016-05-01"),
+    );
+    assert_ne!(
+        get("https://api.kingtime.jp/v1.0/employees/1000"),
+        get("https://api.kingtime.jp/v1.0/employees/2000"),
+    );
+    assert_ne!(
+        get("https://api.kingtime.jp/v1.0/daily-workings?date=2016-05-01"),
+        get("https://api.kingtime.jp/v1.0/daily-workings?date=2016-05-02"),
+    );
+    assert_eq!(
+        post("token", r#"{"code": "1", "note": "token"}"#),
+        post("other", r#"{"code": "1", "note": "other"}"#),
+    );
+    assert_ne!(
+        post("token", r#"{"code": "1"}"#),
+        post("token", r#"{"code": "2"}"#)
+    );
+    assert_ne!(
+        post("token", r#"{"code": "1"}"#),
+        request(
+            Method::PUT,
+            "https://api.kingtime.jp/v1.0/daily-workings/timerecord/8b6ee646",
+            "token",
+            Some(r#"{"code": "1"}"#),
+        ),
+    );
+}
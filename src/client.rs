@@ -0,0 +1,1945 @@
+#[cfg(all(feature = "cassette", not(target_arch = "wasm32")))]
+use crate::cassette::{Cassette, Mode};
+use crate::dry_run::{DryRun, PlannedRequest};
+use crate::endpoint::Endpoint;
+use crate::envelope::ResponseEnvelope;
+use crate::error::{ErrorData, ErrorResponse};
+use crate::hooks::{Hooks, RequestInfo, ResponseInfo};
+use crate::metrics::{MetricsSink, NoopMetrics, RequestMetrics};
+use crate::rate_limit::{self, RateLimitInfo};
+use crate::retry::{Failure, RetryPolicy};
+use crate::token::{AccessTokenProvider, SecretToken};
+use crate::trace;
+#[cfg(all(
+    feature = "isahc",
+    not(feature = "reqwest"),
+    not(target_arch = "wasm32")
+))]
+use crate::transport::IsahcTransport;
+#[cfg(feature = "reqwest")]
+use crate::transport::ReqwestTransport;
+use crate::transport::{HttpRequest, HttpResponse, Transport};
+use crate::{Error, Result};
+use http::header::{self, HeaderMap, HeaderValue};
+use http::{Method, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt;
+#[cfg(all(feature = "cassette", not(target_arch = "wasm32")))]
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use url::Url;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+/// Where the API is served unless overridden with [`ClientBuilder::base_url`].
+pub const DEFAULT_BASE_URL: &str = "https://api.kingtime.jp/";
+/// The API revision used unless overridden with [`ClientBuilder::api_version`].
+pub const DEFAULT_API_VERSION: &str = "v1.0";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The `User-Agent` sent unless overridden with [`ClientBuilder::user_agent`].
+pub const DEFAULT_USER_AGENT: &str = concat!("kingtime-rs/", env!("CARGO_PKG_VERSION"));
+
+/// A handle to the KoT API.
+///
+/// The underlying connection pool is shared by every call made through the
+/// same `Client` (and its clones), so prefer keeping one around over using the
+/// free functions in each module, which build a one-shot client per call.
+#[derive(Clone)]
+pub struct Client {
+    transport: Arc<dyn Transport>,
+    token: Arc<dyn AccessTokenProvider>,
+    base_url: Url,
+    user_agent: HeaderValue,
+    // `None` leaves it to the transport
+    timeout: Option<Duration>,
+    retry: Option<RetryPolicy>,
+    options: RequestOptions,
+    hooks: Hooks,
+    metrics: Arc<dyn MetricsSink>,
+    dry_run: Option<DryRun>,
+    rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
+}
+
+// what `Client::send_inner` tells about its attempts
+#[derive(Default)]
+struct Attempts {
+    retries: u32,
+    status: Option<StatusCode>,
+}
+
+/// Per-call settings overriding the ones the client was built with.
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    pub timeout: Option<Duration>,
+    /// Retries POST and PUT requests per the client's [`RetryPolicy`] even
+    /// if they may have reached the server, at the risk of e.g. recording
+    /// the same punch twice.
+    pub retry_non_idempotent: bool,
+}
+
+impl Client {
+    /// A client with default settings.
+    ///
+    /// # Panics
+    ///
+    /// If `access_token` cannot be sent in a header; use [`Client::builder`]
+    /// to get an [`Error::InvalidToken`] instead.
+    pub fn new(access_token: impl Into<SecretToken>) -> Self {
+        Client::builder()
+            .access_token(access_token)
+            .build()
+            .expect("Client::new()")
+    }
+
+    /// A client with default settings sending its requests through `transport`.
+    pub fn with_transport(transport: impl Transport, access_token: impl Into<SecretToken>) -> Self {
+        Client::builder()
+            .access_token(access_token)
+            .transport(transport)
+            .build()
+            .expect("Client::with_transport()")
+    }
+
+    /// A client with default settings sending its requests through `http`,
+    /// sharing its connection pool, TLS and proxy settings, default headers
+    /// and timeout with the rest of the application. The authorization and
+    /// content type headers are still added to each request only.
+    #[cfg(feature = "reqwest")]
+    pub fn with_http_client(http: reqwest::Client, access_token: impl Into<SecretToken>) -> Self {
+        Client::builder()
+            .access_token(access_token)
+            .http_client(http)
+            .build()
+            .expect("Client::with_http_client()")
+    }
+
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// Returns a client sharing the connection pool with `self` whose calls
+    /// are made with `options`.
+    ///
+    /// ```no_run
+    /// # async fn run(client: kingtime::Client) -> kingtime::Result<()> {
+    /// use kingtime::RequestOptions;
+    /// use std::time::Duration;
+    ///
+    /// let options = RequestOptions {
+    ///     timeout: Some(Duration::from_secs(5)),
+    ///     ..Default::default()
+    /// };
+    /// client.with_options(options).daily_workings().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_options(&self, options: RequestOptions) -> Client {
+        Client {
+            options,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a client sharing the connection pool with `self` that reports
+    /// every call it makes to `sink`, failed ones included.
+    pub fn with_metrics(&self, sink: impl MetricsSink) -> Client {
+        Client {
+            metrics: Arc::new(sink),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a client sharing the connection pool with `self` that, if
+    /// `enabled`, builds and validates every POST, PUT and DELETE request
+    /// but logs it to [`Client::planned_requests`] instead of sending it.
+    /// Calls returning nothing succeed as if answered with `204 No Content`,
+    /// the others, such as [`Client::update_employee`], fail with
+    /// [`Error::Planned`]; GET requests are still sent.
+    ///
+    /// ```no_run
+    /// # async fn run(client: kingtime::Client, req: kingtime::daily_workings::timerecord::Request) -> kingtime::Result<()> {
+    /// let dry_run = client.dry_run(true);
+    /// dry_run.post_timerecord(&"8b6ee646".into(), &req).await?;
+    /// for planned in dry_run.planned_requests() {
+    ///     println!("{} {} {:?}", planned.method, planned.url, planned.body_json);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dry_run(&self, enabled: bool) -> Client {
+        Client {
+            dry_run: enabled.then(DryRun::default),
+            ..self.clone()
+        }
+    }
+
+    /// The requests held back so far by this client in dry-run mode, and
+    /// its clones, in order.
+    pub fn planned_requests(&self) -> Vec<PlannedRequest> {
+        self.dry_run
+            .as_ref()
+            .map(DryRun::planned)
+            .unwrap_or_default()
+    }
+
+    /// The quota reported with the most recent response received by this
+    /// client or any of its clones.
+    pub fn last_rate_limit(&self) -> Option<RateLimitInfo> {
+        *self.rate_limit.lock().unwrap()
+    }
+
+    // `path` is relative to the base URL, e.g. "employees/1000".
+    fn url(&self, path: &str) -> Result<Url> {
+        self.base_url
+            .join(path.trim_start_matches('/'))
+            .map_err(|e| Error::Builder(format!("invalid path {:?}: {}", path, e)))
+    }
+
+    fn headers(&self, authorization: HeaderValue) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            "application/json; charset=utf-8".parse().unwrap(),
+        );
+        headers.insert(header::AUTHORIZATION, authorization);
+        headers.insert(header::USER_AGENT, self.user_agent.clone());
+        headers
+    }
+
+    fn prepare(
+        &self,
+        method: Method,
+        url: Url,
+        body: Option<Vec<u8>>,
+        access_token: &SecretToken,
+    ) -> Result<HttpRequest> {
+        Ok(HttpRequest {
+            method,
+            url,
+            headers: self.headers(authorization(access_token)?),
+            body,
+            timeout: self.options.timeout.or(self.timeout),
+        })
+    }
+
+    async fn execute<D: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: Url,
+        body: Option<Vec<u8>>,
+    ) -> Result<ResponseEnvelope<D>> {
+        let started = Instant::now();
+        let path = url.path().strip_prefix(self.base_url.path());
+        let endpoint = Endpoint::of(path.unwrap_or_default());
+        let mut attempts = Attempts::default();
+        let res = self
+            .authorize_and_send(method.clone(), url, body, endpoint, &mut attempts)
+            .await;
+        self.metrics.record(&RequestMetrics {
+            endpoint,
+            method,
+            status: attempts.status,
+            elapsed: started.elapsed(),
+            retries: attempts.retries,
+            error: res.as_ref().err().map(Error::kind),
+        });
+        res
+    }
+
+    async fn authorize_and_send<D: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: Url,
+        body: Option<Vec<u8>>,
+        endpoint: Endpoint,
+        attempts: &mut Attempts,
+    ) -> Result<ResponseEnvelope<D>> {
+        let access_token = self.token.token().await.map_err(Error::Token)?;
+        let access_token = SecretToken::new(trim_token(&access_token));
+        let req = self.prepare(method, url, body, &access_token)?;
+        let span = trace::request(&req, &access_token, endpoint);
+        trace::instrument(span.clone(), self.send(req, &access_token, span, attempts)).await
+    }
+
+    async fn send<D: DeserializeOwned>(
+        &self,
+        req: HttpRequest,
+        access_token: &SecretToken,
+        span: trace::Span,
+        attempts: &mut Attempts,
+    ) -> Result<ResponseEnvelope<D>> {
+        let started = Instant::now();
+        let mut res = self.send_inner(req, access_token, &span, attempts).await;
+        let elapsed = started.elapsed();
+        trace::finish(&span, &res, elapsed);
+        if let Ok(envelope) = &mut res {
+            envelope.elapsed = elapsed;
+        }
+        res
+    }
+
+    async fn send_inner<D: DeserializeOwned>(
+        &self,
+        req: HttpRequest,
+        access_token: &SecretToken,
+        span: &trace::Span,
+        attempts: &mut Attempts,
+    ) -> Result<ResponseEnvelope<D>> {
+        // Requests that may change something on the server are only sent
+        // again if they provably never made it there, unless asked to.
+        let idempotent = self.options.retry_non_idempotent
+            || self
+                .retry
+                .as_ref()
+                .is_some_and(|p| p.applies_to(&req.method));
+
+        let planned = self
+            .dry_run
+            .as_ref()
+            .and_then(|dry_run| dry_run.plan(&req, access_token));
+        if let Some(planned) = planned {
+            trace::status(span, StatusCode::NO_CONTENT);
+            // The calls ignoring the response get by with an empty one, the
+            // others have nothing to return.
+            return match serde_json::from_slice(b"null") {
+                Ok(data) => Ok(ResponseEnvelope {
+                    data,
+                    status: StatusCode::NO_CONTENT,
+                    headers: Default::default(),
+                    elapsed: Duration::default(),
+                }),
+                Err(_) => Err(Error::Planned(Box::new(planned))),
+            };
+        }
+        let mut attempt = 1;
+        let resp = loop {
+            self.hooks.request(&req, access_token);
+            let res = self.transport.send(req.clone()).await;
+            attempts.retries = attempt - 1;
+            attempts.status = res.as_ref().ok().map(|resp| resp.status);
+            if let Ok(resp) = &res {
+                self.hooks.response(resp);
+                *self.rate_limit.lock().unwrap() = RateLimitInfo::from_headers(&resp.headers);
+            }
+            if let Some(policy) = &self.retry {
+                let (failure, retry_after) = match &res {
+                    Ok(resp) if resp.status.is_success() => (None, None),
+                    Ok(resp) => (Some(Failure::Status(resp.status)), retry_after(resp)),
+                    Err(e) => (Some(Failure::Error(e)), None),
+                };
+                let delay = failure
+                    .filter(|failure| idempotent || failure.is_unsent())
+                    .filter(|failure| policy.should_retry(attempt, failure))
+                    .and_then(|_| policy.delay_for(attempt, retry_after));
+                if let Some(delay) = delay {
+                    sleep(delay).await;
+                    attempt += 1;
+                    continue;
+                }
+            }
+            break res?;
+        };
+        trace::status(span, resp.status);
+        if resp.status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(Error::RateLimited {
+                retry_after: retry_after(&resp),
+            });
+        }
+
+        let status = resp.status;
+        if !status.is_success() {
+            return Err(match serde_json::from_slice(&resp.body) {
+                Ok(ErrorResponse { errors }) => Error::Api {
+                    status,
+                    errors: redact_errors(errors, access_token),
+                },
+                Err(_) => Error::Http {
+                    status,
+                    body: truncate(&access_token.redact(&resp.body)),
+                },
+            });
+        }
+        // An empty body (e.g. 204 No Content) decodes like `null`, which is all
+        // that `()` and the endpoints ignoring the response ask for.
+        let body: &[u8] = if resp.body.iter().all(u8::is_ascii_whitespace) {
+            b"null"
+        } else {
+            &resp.body
+        };
+        // errors are occasionally reported with a 200
+        if let Ok(ErrorResponse { errors }) = serde_json::from_slice(body) {
+            return Err(Error::Api {
+                status,
+                errors: redact_errors(errors, access_token),
+            });
+        }
+        let data = decode(body).map_err(|(path, source)| Error::Decode {
+            body: truncate(&access_token.redact(&resp.body)),
+            path,
+            source,
+        })?;
+        Ok(ResponseEnvelope {
+            data,
+            status,
+            headers: resp.headers,
+            elapsed: Duration::default(),
+        })
+    }
+
+    pub(crate) async fn request<Q, S, D>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<&Q>,
+        body: Option<&S>,
+    ) -> Result<D>
+    where
+        Q: Serialize + ?Sized,
+        S: Serialize + ?Sized,
+        D: DeserializeOwned,
+    {
+        Ok(self
+            .request_with_meta(method, path, query, body)
+            .await?
+            .into_data())
+    }
+
+    /// Sends `body` as JSON to `path`, with `query` appended as the query
+    /// string. Every endpoint goes through here.
+    pub(crate) async fn request_with_meta<Q, S, D>(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<&Q>,
+        body: Option<&S>,
+    ) -> Result<ResponseEnvelope<D>>
+    where
+        Q: Serialize + ?Sized,
+        S: Serialize + ?Sized,
+        D: DeserializeOwned,
+    {
+        let mut url = self.url(path)?;
+        if let Some(query) = query {
+            let query = serde_urlencoded::to_string(query)
+                .map_err(|e| Error::Builder(format!("invalid query: {}", e)))?;
+            if !query.is_empty() {
+                url.set_query(Some(&query));
+            }
+        }
+        let body = match body {
+            Some(body) => Some(serde_json::to_vec(body).map_err(Error::Encode)?),
+            None => None,
+        };
+        self.execute(method, url, body).await
+    }
+
+    pub(crate) async fn get<D: DeserializeOwned>(&self, path: &str) -> Result<D> {
+        self.request(Method::GET, path, None::<&()>, None::<&()>)
+            .await
+    }
+
+    pub(crate) async fn get_with_query<D: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &impl Serialize,
+    ) -> Result<D> {
+        self.request(Method::GET, path, Some(query), None::<&()>)
+            .await
+    }
+
+    pub(crate) async fn post<S: Serialize + ?Sized, D: DeserializeOwned>(
+        &self,
+        path: &str,
+        payload: &S,
+    ) -> Result<D> {
+        self.request(Method::POST, path, None::<&()>, Some(payload))
+            .await
+    }
+
+    pub(crate) async fn put<S: Serialize + ?Sized, D: DeserializeOwned>(
+        &self,
+        path: &str,
+        payload: &S,
+    ) -> Result<D> {
+        self.request(Method::PUT, path, None::<&()>, Some(payload))
+            .await
+    }
+
+    pub(crate) async fn delete<D: DeserializeOwned>(&self, path: &str) -> Result<D> {
+        self.request(Method::DELETE, path, None::<&()>, None::<&()>)
+            .await
+    }
+}
+
+// The token may come from a provider, which has nothing to show; either way
+// it must not end up in the output.
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("access_token", &"[REDACTED]")
+            .field("base_url", &self.base_url.as_str())
+            .field("user_agent", &self.user_agent)
+            .field("timeout", &self.timeout)
+            .field("retry", &self.retry)
+            .field("options", &self.options)
+            .field("dry_run", &self.dry_run.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+// not tied to any runtime, like the transports
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    futures_timer::Delay::new(duration).await
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await
+}
+
+// On failure, also tells where in the document it happened, e.g.
+// `[3].dailyWorkings[0].timeRecord[2].time`, unless that is the root.
+fn decode<D: DeserializeOwned>(
+    body: &[u8],
+) -> std::result::Result<D, (Option<String>, serde_json::Error)> {
+    let mut de = serde_json::Deserializer::from_slice(body);
+    let data = serde_path_to_error::deserialize(&mut de).map_err(|e| {
+        let path = e.path().to_string();
+        let path = if path == "." { None } else { Some(path) };
+        (path, e.into_inner())
+    })?;
+    de.end().map_err(|e| (None, e))?;
+    Ok(data)
+}
+
+const MAX_ERROR_BODY: usize = 4096;
+
+// keeps error messages readable when the server sends back a whole HTML page
+fn truncate(body: &str) -> String {
+    match body.char_indices().nth(MAX_ERROR_BODY) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body.to_string(),
+    }
+}
+
+// Errors end up in logs and chat messages, so nothing the server echoes back
+// may carry the token along.
+fn redact_errors(mut errors: Vec<ErrorData>, access_token: &SecretToken) -> Vec<ErrorData> {
+    for error in &mut errors {
+        error.message = access_token.redact(error.message.as_bytes());
+    }
+    errors
+}
+
+fn retry_after(resp: &HttpResponse) -> Option<Duration> {
+    if resp.status == StatusCode::TOO_MANY_REQUESTS {
+        rate_limit::retry_after(&resp.headers, chrono::Utc::now())
+    } else {
+        None
+    }
+}
+
+#[derive(Default)]
+pub struct ClientBuilder {
+    access_token: Option<SecretToken>,
+    token_provider: Option<Arc<dyn AccessTokenProvider>>,
+    base_url: Option<String>,
+    api_version: Option<String>,
+    timeout: Option<Duration>,
+    retry: Option<RetryPolicy>,
+    proxy: Option<String>,
+    proxy_auth: Option<(String, String)>,
+    no_system_proxy: bool,
+    no_compression: bool,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Option<Duration>>,
+    tcp_keepalive: Option<Option<Duration>>,
+    user_agent: Option<String>,
+    transport: Option<Arc<dyn Transport>>,
+    // the timeout is left to the transport unless set explicitly
+    transport_timeout: bool,
+    #[cfg(all(feature = "cassette", not(target_arch = "wasm32")))]
+    cassette: Option<(PathBuf, Mode)>,
+    hooks: Hooks,
+}
+
+impl ClientBuilder {
+    pub fn access_token(mut self, access_token: impl Into<SecretToken>) -> Self {
+        self.access_token = Some(access_token.into());
+        self.token_provider = None;
+        self
+    }
+
+    /// Asks `provider` for the access token before each request, replacing
+    /// the one set with [`ClientBuilder::access_token`].
+    pub fn token_provider(mut self, provider: impl AccessTokenProvider) -> Self {
+        self.token_provider = Some(Arc::new(provider));
+        self.access_token = None;
+        self
+    }
+
+    /// Overrides the host serving the API, `https://api.kingtime.jp` by
+    /// default. The API version is appended to it, and endpoint paths are
+    /// resolved relative to that.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Overrides the version segment of every URL, [`DEFAULT_API_VERSION`]
+    /// by default.
+    pub fn api_version(mut self, version: impl Into<String>) -> Self {
+        self.api_version = Some(version.into());
+        self
+    }
+
+    /// Total time allowed for each request, 30 seconds by default.
+    /// Calls exceeding it fail with [`Error::Timeout`]. Not enforced on wasm,
+    /// where fetch has no timeouts.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retries failed requests according to `policy`. Nothing is retried by default.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Sends every request through the proxy at `url`, e.g. `http://proxy:8080`.
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Credentials for the proxy set with [`ClientBuilder::proxy`].
+    pub fn proxy_basic_auth(mut self, username: &str, password: &str) -> Self {
+        self.proxy_auth = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Whether to honor the `HTTP_PROXY`/`HTTPS_PROXY`/`NO_PROXY` environment
+    /// variables, which is the default.
+    pub fn system_proxy(mut self, enabled: bool) -> Self {
+        self.no_system_proxy = !enabled;
+        self
+    }
+
+    /// Whether to ask for gzip, brotli or deflate compressed responses, which
+    /// is the default. Turning it off makes the traffic readable in debugging
+    /// proxies.
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.no_compression = !enabled;
+        self
+    }
+
+    /// Most idle connections kept open to the API, unlimited by default.
+    /// Ignored on wasm, like the other connection settings.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// How long an idle connection is kept open for reuse, 90 seconds by
+    /// default; `None` keeps it until the server closes it. Set it below the
+    /// server's own idle timeout to avoid reusing connections it is about to
+    /// close.
+    pub fn pool_idle_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.pool_idle_timeout = Some(timeout.into());
+        self
+    }
+
+    /// Interval of TCP keep-alive probes on open connections, none by
+    /// default.
+    pub fn tcp_keepalive(mut self, interval: impl Into<Option<Duration>>) -> Self {
+        self.tcp_keepalive = Some(interval.into());
+        self
+    }
+
+    /// Replaces the [`DEFAULT_USER_AGENT`]. To identify both your application
+    /// and this crate, include the default:
+    ///
+    /// ```
+    /// let builder = kingtime::Client::builder().user_agent(format!(
+    ///     "attendance-bot/2.1 {}",
+    ///     kingtime::DEFAULT_USER_AGENT
+    /// ));
+    /// ```
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sends requests through `transport` instead of a reqwest client built
+    /// from this builder's settings. The proxy and connection pool settings
+    /// are ignored then.
+    pub fn transport(mut self, transport: impl Transport) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self.transport_timeout = false;
+        self
+    }
+
+    /// Sends requests through `http`, like [`ClientBuilder::transport`].
+    /// Unless [`ClientBuilder::timeout`] is set too, the timeout `http` was
+    /// built with applies.
+    #[cfg(feature = "reqwest")]
+    pub fn http_client(mut self, http: reqwest::Client) -> Self {
+        self.transport = Some(Arc::new(ReqwestTransport::new(http)));
+        self.transport_timeout = true;
+        self
+    }
+
+    /// Records the exchanges with the API to `dir`, or replays them from it,
+    /// as told by `mode`. Works with any transport, see [`crate::cassette`].
+    #[cfg(all(feature = "cassette", not(target_arch = "wasm32")))]
+    pub fn cassette(mut self, dir: impl Into<PathBuf>, mode: Mode) -> Self {
+        self.cassette = Some((dir.into(), mode));
+        self
+    }
+
+    /// Calls `hook` before every attempt at sending a request, retries
+    /// included.
+    ///
+    /// ```no_run
+    /// let client = kingtime::Client::builder()
+    ///     .access_token("token")
+    ///     .on_request(|req| println!("{} {}", req.method, req.url))
+    ///     .build()?;
+    /// # Ok::<(), kingtime::Error>(())
+    /// ```
+    pub fn on_request(mut self, hook: impl Fn(&RequestInfo) + Send + Sync + 'static) -> Self {
+        self.hooks.on_request.push(Arc::new(hook));
+        self
+    }
+
+    /// Calls `hook` with every response received, including the ones that
+    /// are retried or turned into errors.
+    pub fn on_response(mut self, hook: impl Fn(&ResponseInfo) + Send + Sync + 'static) -> Self {
+        self.hooks.on_response.push(Arc::new(hook));
+        self
+    }
+
+    pub fn build(self) -> Result<Client> {
+        let token: Arc<dyn AccessTokenProvider> = match (&self.access_token, &self.token_provider) {
+            (Some(access_token), _) => {
+                // a static token is checked once here rather than on every call
+                authorization(access_token)?;
+                Arc::new(access_token.clone())
+            }
+            (None, Some(provider)) => provider.clone(),
+            (None, None) => return Err(Error::Builder("access token is not set".into())),
+        };
+        let transport = match &self.transport {
+            Some(transport) => transport.clone(),
+            None => self.default_transport()?,
+        };
+        #[cfg(all(feature = "cassette", not(target_arch = "wasm32")))]
+        let transport: Arc<dyn Transport> = match &self.cassette {
+            Some((dir, mode)) => Arc::new(Cassette::with_arc(dir.clone(), *mode, transport)),
+            None => transport,
+        };
+        let base_url = parse_base_url(
+            self.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL),
+            self.api_version.as_deref().unwrap_or(DEFAULT_API_VERSION),
+        )?;
+        let user_agent = match &self.user_agent {
+            Some(user_agent) => user_agent
+                .parse()
+                .map_err(|_| Error::Builder(format!("invalid user agent {:?}", user_agent)))?,
+            None => HeaderValue::from_static(DEFAULT_USER_AGENT),
+        };
+        Ok(Client {
+            transport,
+            token,
+            base_url,
+            user_agent,
+            timeout: match self.timeout {
+                Some(timeout) => Some(timeout),
+                None if self.transport_timeout => None,
+                None => Some(DEFAULT_TIMEOUT),
+            },
+            retry: self.retry,
+            options: RequestOptions::default(),
+            hooks: self.hooks,
+            metrics: Arc::new(NoopMetrics),
+            dry_run: None,
+            rate_limit: Arc::default(),
+        })
+    }
+}
+
+// whitespace is never part of a token, but easily read along from a file
+fn trim_token(access_token: &str) -> &str {
+    access_token.trim_matches(|c: char| c.is_ascii_whitespace())
+}
+
+// The token itself is left out of the messages, they may end up in logs.
+fn authorization(access_token: &SecretToken) -> Result<HeaderValue> {
+    let access_token = trim_token(access_token.expose());
+    if access_token.is_empty() {
+        return Err(Error::InvalidToken("access token is empty".into()));
+    }
+    if let Some((i, c)) = access_token
+        .char_indices()
+        .find(|&(_, c)| !c.is_ascii_graphic())
+    {
+        return Err(Error::InvalidToken(format!(
+            "access token contains {:?} at byte {}",
+            c, i
+        )));
+    }
+    let mut value: HeaderValue = format!("Bearer {}", access_token).parse().unwrap();
+    value.set_sensitive(true);
+    Ok(value)
+}
+
+impl ClientBuilder {
+    #[cfg(feature = "reqwest")]
+    fn default_transport(&self) -> Result<Arc<dyn Transport>> {
+        Ok(Arc::new(ReqwestTransport::new(self.reqwest_client()?)))
+    }
+
+    #[cfg(all(
+        feature = "isahc",
+        not(feature = "reqwest"),
+        not(target_arch = "wasm32")
+    ))]
+    fn default_transport(&self) -> Result<Arc<dyn Transport>> {
+        use isahc::auth::{Authentication, Credentials};
+        use isahc::config::Configurable;
+
+        let mut http = isahc::HttpClient::builder();
+        if self.no_compression {
+            http = http.automatic_decompression(false);
+        }
+        if let Some(interval) = self.tcp_keepalive.flatten() {
+            http = http.tcp_keepalive(interval);
+        }
+        if let Some(url) = &self.proxy {
+            let uri = url
+                .parse()
+                .map_err(|e| Error::Builder(format!("invalid proxy url {:?}: {}", url, e)))?;
+            http = http.proxy(Some(uri));
+            if let Some((username, password)) = &self.proxy_auth {
+                http = http
+                    .proxy_authentication(Authentication::basic())
+                    .proxy_credentials(Credentials::new(username.as_str(), password.as_str()));
+            }
+        } else if self.no_system_proxy {
+            http = http.proxy(None);
+        }
+        Ok(Arc::new(IsahcTransport::new(http.build()?)))
+    }
+
+    #[cfg(not(any(
+        feature = "reqwest",
+        all(feature = "isahc", not(target_arch = "wasm32"))
+    )))]
+    fn default_transport(&self) -> Result<Arc<dyn Transport>> {
+        Err(Error::Builder(
+            "no HTTP stack: enable the `reqwest` or `isahc` feature, or set a transport".into(),
+        ))
+    }
+
+    #[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+    fn reqwest_client(&self) -> Result<reqwest::Client> {
+        let mut http = reqwest::Client::builder();
+        if self.no_compression {
+            http = http.no_gzip().no_brotli().no_deflate();
+        }
+        if self.no_system_proxy {
+            http = http.no_proxy();
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            http = http.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            http = http.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            http = http.tcp_keepalive(interval);
+        }
+        if let Some(url) = &self.proxy {
+            let mut proxy = reqwest::Proxy::all(url)
+                .map_err(|e| Error::Builder(format!("invalid proxy url {:?}: {}", url, e)))?;
+            if let Some((username, password)) = &self.proxy_auth {
+                proxy = proxy.basic_auth(username, password);
+            }
+            if !self.no_system_proxy {
+                proxy = proxy.no_proxy(reqwest::NoProxy::from_env());
+            }
+            http = http.proxy(proxy);
+        }
+        Ok(http.build()?)
+    }
+
+    // the browser takes care of proxies
+    #[cfg(all(feature = "reqwest", target_arch = "wasm32"))]
+    fn reqwest_client(&self) -> Result<reqwest::Client> {
+        if self.proxy.is_some() {
+            return Err(Error::Builder("proxies are not supported on wasm".into()));
+        }
+        Ok(reqwest::Client::new())
+    }
+}
+
+impl fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("access_token", &self.access_token)
+            .field(
+                "token_provider",
+                &self.token_provider.as_ref().map(|_| ".."),
+            )
+            .field("base_url", &self.base_url)
+            .field("api_version", &self.api_version)
+            .field("timeout", &self.timeout)
+            .field("retry", &self.retry)
+            .field("proxy", &self.proxy)
+            .field(
+                "proxy_auth",
+                &self
+                    .proxy_auth
+                    .as_ref()
+                    .map(|(username, _)| (username, "[REDACTED]")),
+            )
+            .field("system_proxy", &!self.no_system_proxy)
+            .field("compression", &!self.no_compression)
+            .field("pool_max_idle_per_host", &self.pool_max_idle_per_host)
+            .field("pool_idle_timeout", &self.pool_idle_timeout)
+            .field("tcp_keepalive", &self.tcp_keepalive)
+            .field("user_agent", &self.user_agent)
+            .finish_non_exhaustive()
+    }
+}
+
+// The versioned API root, ending with a slash so that `Url::join` appends to
+// it rather than replacing the last segment.
+fn parse_base_url(base_url: &str, version: &str) -> Result<Url> {
+    let mut url: Url = base_url
+        .parse()
+        .map_err(|e| Error::Builder(format!("invalid base url {:?}: {}", base_url, e)))?;
+    if url.cannot_be_a_base() {
+        return Err(Error::Builder(format!("invalid base url {:?}", base_url)));
+    }
+    if version.is_empty() || version.contains(['/', '?', '#']) {
+        return Err(Error::Builder(format!("invalid api version {:?}", version)));
+    }
+    let path = url.path().trim_end_matches('/').to_string();
+    url.set_path(&format!("{}/{}/", path, version));
+    Ok(url)
+}
+
+#[test]
+fn join_base_url() {
+    for base in &["http://127.0.0.1:8080", "http://127.0.0.1:8080/"] {
+        let client = Client::builder()
+            .access_token("token")
+            .base_url(*base)
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.url("employees/1000").unwrap().as_str(),
+            "http://127.0.0.1:8080/v1.0/employees/1000"
+        );
+        assert_eq!(
+            client.url("/employees/1000").unwrap().as_str(),
+            "http://127.0.0.1:8080/v1.0/employees/1000"
+        );
+    }
+
+    let client = Client::new("token");
+    assert_eq!(
+        client.url("daily-workings").unwrap().as_str(),
+        "https://api.kingtime.jp/v1.0/daily-workings"
+    );
+
+    let client = Client::builder()
+        .access_token("token")
+        .base_url("http://127.0.0.1:8080/kot/")
+        .build()
+        .unwrap();
+    assert_eq!(
+        client.url("daily-workings").unwrap().as_str(),
+        "http://127.0.0.1:8080/kot/v1.0/daily-workings"
+    );
+}
+
+#[test]
+fn reject_invalid_api_version() {
+    for version in &["", "v1.0/", "v1?", "v#1"] {
+        let res = Client::builder()
+            .access_token("token")
+            .api_version(*version)
+            .build();
+        assert!(matches!(res, Err(Error::Builder(_))), "{}", version);
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn override_api_version() {
+    use crate::mock::{json, FakeTransport};
+
+    let transport = FakeTransport::new(|_| json(200, "[]"));
+    let client = Client::builder()
+        .access_token("token")
+        .api_version("v1.1")
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    let date = "2016-05-01".parse().unwrap();
+    client.daily_workings().await.unwrap();
+    client.timerecords(&["a".into()], date, date).await.unwrap();
+    client.employee(&"1000".into()).await.unwrap_err();
+    let urls: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|req| req.url.path().to_string())
+        .collect();
+    assert_eq!(
+        urls,
+        [
+            "/v1.1/daily-workings",
+            "/v1.1/daily-workings/timerecord",
+            "/v1.1/employees/1000"
+        ]
+    );
+}
+
+#[test]
+fn reject_invalid_base_url() {
+    for base in &["not a url", "127.0.0.1:8080", "mailto:kot@example.com"] {
+        let res = Client::builder()
+            .access_token("token")
+            .base_url(*base)
+            .build();
+        assert!(matches!(res, Err(Error::Builder(_))), "{}", base);
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn employee_via_base_url() {
+    use crate::mock::{MockServer, Response};
+
+    let server = MockServer::start(|_| {
+        Response::json(
+            200,
+            r#"{"lastName": "勤怠", "firstName": "太郎", "key": "8b6ee646"}"#,
+        )
+    })
+    .await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .build()
+        .unwrap();
+
+    let resp = client.employee(&"1000".into()).await.unwrap();
+    assert_eq!(resp.key, "8b6ee646");
+    assert_eq!(server.requests()[0].target, "/v1.0/employees/1000");
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn timeout() {
+    use crate::mock::{MockServer, Response};
+
+    let server =
+        MockServer::start(|_| Response::json(200, "{}").delay(Duration::from_millis(300))).await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .timeout(Duration::from_millis(50))
+        .build()
+        .unwrap();
+
+    let res: Result<serde_json::Value> = client.get("daily-workings").await;
+    assert!(matches!(res, Err(Error::Timeout(_))), "{:?}", res);
+
+    let res: Result<serde_json::Value> = client
+        .with_options(RequestOptions {
+            timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        })
+        .get("daily-workings")
+        .await;
+    assert!(res.is_ok(), "{:?}", res);
+}
+
+#[cfg(test)]
+fn flaky_server(failures: usize) -> impl Fn(&crate::mock::Request) -> crate::mock::Response {
+    use crate::mock::Response;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let count = AtomicUsize::new(0);
+    move |_| {
+        if count.fetch_add(1, Ordering::SeqCst) < failures {
+            Response::json(503, "<html>Service Unavailable</html>")
+        } else {
+            Response::json(200, "{}")
+        }
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn retry_get() {
+    use crate::mock::MockServer;
+
+    let server = MockServer::start(flaky_server(2)).await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .retry(RetryPolicy::new(3).base_delay(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    let res: Result<serde_json::Value> = client.get("daily-workings").await;
+    assert!(res.is_ok(), "{:?}", res);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn retry_gives_up() {
+    use crate::mock::MockServer;
+
+    let server = MockServer::start(flaky_server(5)).await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .retry(RetryPolicy::new(3).base_delay(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    let res: Result<serde_json::Value> = client.get("daily-workings").await;
+    assert!(res.is_err());
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn retry_post_only_when_enabled() {
+    use crate::mock::MockServer;
+
+    let server = MockServer::start(flaky_server(1)).await;
+    let policy = RetryPolicy::new(3).base_delay(Duration::from_millis(1));
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .retry(policy.clone())
+        .build()
+        .unwrap();
+    let res: Result<serde_json::Value> = client.post("daily-workings/timerecord/x", &()).await;
+    assert!(res.is_err());
+    assert_eq!(server.requests().len(), 1);
+
+    let server = MockServer::start(flaky_server(1)).await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .retry(policy.retry_non_idempotent(true))
+        .build()
+        .unwrap();
+    let res: Result<serde_json::Value> = client.post("daily-workings/timerecord/x", &()).await;
+    assert!(res.is_ok(), "{:?}", res);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn retry_post_only_if_unsent() {
+    use crate::mock::{MockServer, Response};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // counts attempts that never reach a server
+    #[derive(Clone, Default)]
+    struct Counting(ReqwestTransport, Arc<AtomicUsize>);
+
+    impl Transport for Counting {
+        fn send(&self, req: HttpRequest) -> crate::BoxFuture<'_, Result<HttpResponse>> {
+            self.1.fetch_add(1, Ordering::SeqCst);
+            self.0.send(req)
+        }
+    }
+
+    let policy = RetryPolicy::new(3).base_delay(Duration::from_millis(1));
+    let client = |base_url: String, transport: Option<Counting>| {
+        let builder = Client::builder()
+            .access_token("token")
+            .base_url(base_url)
+            .retry(policy.clone());
+        match transport {
+            Some(transport) => builder.transport(transport),
+            None => builder,
+        }
+        .build()
+        .unwrap()
+    };
+
+    // connection refused: the punch cannot have been recorded
+    let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", closed.local_addr().unwrap());
+    drop(closed);
+    let transport = Counting::default();
+    let res: Result<serde_json::Value> = client(url, Some(transport.clone()))
+        .post("daily-workings/timerecord/x", &())
+        .await;
+    assert!(
+        matches!(&res, Err(e) if e.kind() == crate::ErrorKind::Connect),
+        "{:?}",
+        res
+    );
+    assert_eq!(transport.1.load(Ordering::SeqCst), 3);
+
+    // connection lost after sending: the punch may have been recorded
+    let server = MockServer::start(|_| Response::hang_up()).await;
+    let res: Result<serde_json::Value> = client(server.url("/"), None)
+        .post("daily-workings/timerecord/x", &())
+        .await;
+    assert!(matches!(res, Err(Error::Reqwest(_))), "{:?}", res);
+    assert_eq!(server.requests().len(), 1);
+
+    let res: Result<serde_json::Value> = client(server.url("/"), None)
+        .with_options(RequestOptions {
+            retry_non_idempotent: true,
+            ..Default::default()
+        })
+        .post("daily-workings/timerecord/x", &())
+        .await;
+    assert!(res.is_err());
+    assert_eq!(server.requests().len(), 4);
+
+    let res: Result<serde_json::Value> = client(server.url("/"), None)
+        .delete("daily-workings/timerecord/x")
+        .await;
+    assert!(res.is_err());
+    assert_eq!(server.requests().len(), 7);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn record_rate_limit() {
+    use crate::mock::{MockServer, Response};
+
+    let server = MockServer::start(|req| {
+        if req.target.ends_with("/employees/1000") {
+            Response::json(200, "{}")
+                .header("X-RateLimit-Limit", "500")
+                .header("X-RateLimit-Remaining", "499")
+                .header("X-RateLimit-Reset", "1462028400")
+        } else {
+            Response::json(200, "{}")
+        }
+    })
+    .await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .build()
+        .unwrap();
+    assert_eq!(client.last_rate_limit(), None);
+
+    let _: serde_json::Value = client
+        .with_options(RequestOptions::default())
+        .get("employees/1000")
+        .await
+        .unwrap();
+    assert_eq!(
+        client.last_rate_limit(),
+        Some(RateLimitInfo {
+            limit: Some(500),
+            remaining: Some(499),
+            reset: Some(1462028400),
+        })
+    );
+
+    let _: serde_json::Value = client.get("daily-workings").await.unwrap();
+    assert_eq!(client.last_rate_limit(), None);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn rate_limited() {
+    use crate::mock::{MockServer, Response};
+
+    let server =
+        MockServer::start(|_| Response::json(429, "Too Many Requests").header("Retry-After", "7"))
+            .await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .build()
+        .unwrap();
+
+    let res: Result<serde_json::Value> = client.get("daily-workings").await;
+    match res {
+        Err(Error::RateLimited { retry_after }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(7)))
+        }
+        res => panic!("{:?}", res),
+    }
+
+    // waiting 7 seconds exceeds the cap, so the client gives up immediately
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .retry(RetryPolicy::new(3).max_retry_after(Duration::from_secs(1)))
+        .build()
+        .unwrap();
+    let res: Result<serde_json::Value> = client.get("daily-workings").await;
+    assert!(matches!(res, Err(Error::RateLimited { .. })), "{:?}", res);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn retry_rate_limited() {
+    use crate::mock::{MockServer, Response};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let count = AtomicUsize::new(0);
+    let server = MockServer::start(move |_| {
+        if count.fetch_add(1, Ordering::SeqCst) == 0 {
+            Response::json(429, "Too Many Requests").header("Retry-After", "0")
+        } else {
+            Response::json(200, "{}")
+        }
+    })
+    .await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .retry(RetryPolicy::new(3).base_delay(Duration::from_secs(60)))
+        .build()
+        .unwrap();
+
+    // Retry-After wins over the minute long backoff
+    let res: Result<serde_json::Value> = client.get("daily-workings").await;
+    assert!(res.is_ok(), "{:?}", res);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn route_through_proxy() {
+    use crate::mock::{MockServer, Response};
+
+    let proxy = MockServer::start(|_| Response::json(200, "{}")).await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url("http://kot.invalid")
+        .proxy(proxy.url(""))
+        .proxy_basic_auth("user", "pass")
+        .build()
+        .unwrap();
+
+    let _: serde_json::Value = client.get("employees/1000").await.unwrap();
+    let req = &proxy.requests()[0];
+    assert_eq!(req.target, "http://kot.invalid/v1.0/employees/1000");
+    assert_eq!(
+        req.header("proxy-authorization"),
+        Some("Basic dXNlcjpwYXNz")
+    );
+}
+
+#[test]
+fn reject_invalid_proxy() {
+    let res = Client::builder()
+        .access_token("token")
+        .proxy("not a url")
+        .build();
+    assert!(matches!(res, Err(Error::Builder(_))));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn send_user_agent() {
+    use crate::mock::{MockServer, Response};
+
+    let server = MockServer::start(|_| Response::json(200, "{}")).await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .build()
+        .unwrap();
+    let _: serde_json::Value = client.get("daily-workings").await.unwrap();
+    let _: serde_json::Value = client
+        .get_with_query("daily-workings/timerecord", &[("start", "2016-05-01")])
+        .await
+        .unwrap();
+    let _: serde_json::Value = client
+        .post("daily-workings/timerecord/x", &())
+        .await
+        .unwrap();
+
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .user_agent("attendance-bot/2.1 kingtime-rs/0.3")
+        .build()
+        .unwrap();
+    let _: serde_json::Value = client.get("daily-workings").await.unwrap();
+
+    let agents: Vec<_> = server
+        .requests()
+        .iter()
+        .map(|req| req.header("user-agent").unwrap().to_string())
+        .collect();
+    let default = format!("kingtime-rs/{}", env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        agents,
+        [
+            &*default,
+            &default,
+            &default,
+            "attendance-bot/2.1 kingtime-rs/0.3"
+        ]
+    );
+}
+
+#[test]
+fn reject_invalid_user_agent() {
+    let res = Client::builder()
+        .access_token("token")
+        .user_agent("bot\n")
+        .build();
+    assert!(matches!(res, Err(Error::Builder(_))));
+}
+
+#[test]
+fn reject_invalid_token() {
+    for token in ["tok\nen", "tok\ren", "トークン", "tok en", " \n"] {
+        let res = Client::builder().access_token(token).build();
+        assert!(matches!(res, Err(Error::InvalidToken(_))), "{:?}", token);
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn trim_surrounding_whitespace() {
+    use crate::mock::{json, FakeTransport};
+
+    let transport =
+        FakeTransport::new(|_| json(200, r#"{"lastName": "", "firstName": "", "key": ""}"#));
+    let client = Client::builder()
+        .access_token(" token\r\n")
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    client.employee(&"1000".into()).await.unwrap();
+    assert_eq!(
+        transport.requests()[0].headers[header::AUTHORIZATION],
+        "Bearer token"
+    );
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn query_token_provider() {
+    use crate::mock::{json, FakeTransport};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let transport =
+        FakeTransport::new(|_| json(200, r#"{"lastName": "", "firstName": "", "key": ""}"#));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let client = Client::builder()
+        .token_provider(move || {
+            let n = counter.fetch_add(1, Ordering::SeqCst);
+            async move { Ok::<_, Error>(format!("token-{}", n)) }
+        })
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    client.employee(&"1000".into()).await.unwrap();
+    client.employee(&"1000".into()).await.unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    let tokens: Vec<_> = transport
+        .requests()
+        .iter()
+        .map(|req| req.headers[header::AUTHORIZATION].clone())
+        .collect();
+    assert_eq!(tokens, ["Bearer token-0", "Bearer token-1"]);
+
+    let client = Client::builder()
+        .token_provider(|| async { Err::<String, _>("vault is sealed") })
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+    let e = client.employee(&"1000".into()).await.unwrap_err();
+    assert!(matches!(e.inner(), Error::Token(_)), "{:?}", e);
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[test]
+fn redact_debug_output() {
+    let builder = Client::builder()
+        .access_token("secret-token")
+        .proxy("http://proxy:8080")
+        .proxy_basic_auth("user", "secret-password");
+    let debug = format!("{:?}", builder);
+    assert!(!debug.contains("secret"), "{}", debug);
+
+    let client = builder.build().unwrap();
+    let debug = format!("{:?}", client);
+    assert!(!debug.contains("secret"), "{}", debug);
+    assert!(debug.contains("[REDACTED]"), "{}", debug);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn redact_errors_and_requests() {
+    use crate::mock::{json, FakeTransport, MockServer, Response};
+
+    const TOKEN: &str = "secret-token-1234";
+
+    #[track_caller]
+    fn assert_redacted<T: fmt::Debug>(res: Result<T>) {
+        let e = res.unwrap_err();
+        for output in [format!("{:?}", e), e.to_string()] {
+            assert!(!output.contains(TOKEN), "{}", output);
+        }
+    }
+
+    // a server repeating the token back in every response it can
+    let transport = FakeTransport::new(|req| {
+        let echo = format!("received {}", TOKEN);
+        match req.url.path() {
+            "/v1.0/api" => json(
+                400,
+                &format!(r#"{{"errors": [{{"message": "{}", "code": 1}}]}}"#, echo),
+            ),
+            "/v1.0/api-ok" => json(
+                200,
+                &format!(r#"{{"errors": [{{"message": "{}", "code": 1}}]}}"#, echo),
+            ),
+            "/v1.0/companies" => json(
+                401,
+                &format!(r#"{{"errors": [{{"message": "{}", "code": 102}}]}}"#, echo),
+            ),
+            "/v1.0/http" => json(502, &format!("<html>{}</html>", echo)),
+            "/v1.0/decode" => json(200, &format!("<html>{}</html>", echo)),
+            _ => json(429, ""),
+        }
+    });
+    let client = Client::with_transport(transport.clone(), TOKEN);
+    for path in ["api", "api-ok", "http", "decode", "rate-limited"] {
+        assert_redacted(client.get::<serde_json::Value>(path).await);
+    }
+    assert_redacted(client.ping().await);
+
+    let req = format!("{:?}", transport.requests()[0]);
+    assert!(!req.contains(TOKEN), "{}", req);
+    assert!(
+        req.contains(r#""authorization": "Bearer [REDACTED]""#),
+        "{}",
+        req
+    );
+    let res: Result<serde_json::Value> = client.post("api", &[TOKEN]).await;
+    assert_redacted(res);
+    let req = format!("{:?}", transport.requests().last().unwrap());
+    assert!(!req.contains(TOKEN), "{}", req);
+
+    let server =
+        MockServer::start(|_| Response::json(200, "{}").delay(Duration::from_millis(300))).await;
+    let client = |base_url: String| {
+        Client::builder()
+            .access_token(TOKEN)
+            .base_url(base_url)
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap()
+    };
+    assert_redacted(client(server.url("/")).get::<serde_json::Value>("x").await);
+    // nothing listens on port 1
+    let res = client("http://127.0.0.1:1/".into())
+        .get::<serde_json::Value>("x")
+        .await;
+    assert!(matches!(res, Err(Error::Reqwest(_))), "{:?}", res);
+    assert_redacted(res);
+
+    let invalid = format!("{} {}", TOKEN, TOKEN);
+    assert_redacted(Client::builder().access_token(invalid).build());
+    assert_redacted(
+        Client::builder()
+            .access_token(TOKEN)
+            .base_url("not a url")
+            .build(),
+    );
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn send_each_verb() {
+    use crate::mock::{MockServer, Response};
+    use serde_json::{json, Value};
+
+    let server = MockServer::start(|req| match &*req.method {
+        "DELETE" => Response::json(200, ""),
+        _ => Response::json(200, r#"{"ok": true}"#),
+    })
+    .await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .build()
+        .unwrap();
+
+    let _: Value = client.get("divisions").await.unwrap();
+    let _: Value = client
+        .get_with_query("divisions", &[("date", "2016-05-01")])
+        .await
+        .unwrap();
+    let _: Value = client
+        .post("divisions", &json!({"name": "a"}))
+        .await
+        .unwrap();
+    let _: Value = client
+        .put("divisions/1", &json!({"name": "b"}))
+        .await
+        .unwrap();
+    let () = client.delete("divisions/1").await.unwrap();
+
+    let requests = server.requests();
+    let sent: Vec<_> = requests
+        .iter()
+        .map(|req| {
+            (
+                &*req.method,
+                &*req.target,
+                String::from_utf8_lossy(&req.body).into_owned(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        sent,
+        [
+            ("GET", "/v1.0/divisions", "".into()),
+            ("GET", "/v1.0/divisions?date=2016-05-01", "".into()),
+            ("POST", "/v1.0/divisions", r#"{"name":"a"}"#.into()),
+            ("PUT", "/v1.0/divisions/1", r#"{"name":"b"}"#.to_string()),
+            ("DELETE", "/v1.0/divisions/1", "".into()),
+        ]
+    );
+    for req in &requests {
+        assert_eq!(
+            req.header("content-type"),
+            Some("application/json; charset=utf-8")
+        );
+        assert_eq!(req.header("authorization"), Some("Bearer token"));
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn call_hooks() {
+    use crate::mock::{json, FakeTransport};
+
+    let transport = FakeTransport::new(|req| match req.method {
+        Method::GET => json(200, r#"{"lastName": "", "firstName": "", "key": "a"}"#),
+        _ => json(200, "{}"),
+    });
+    let seen = Arc::new(Mutex::new(vec![]));
+    let (on_request, on_response) = (seen.clone(), seen.clone());
+    let client = Client::builder()
+        .access_token("token")
+        .transport(transport)
+        .on_request(move |req| {
+            on_request.lock().unwrap().push(format!(
+                "{} {} {:?}",
+                req.method,
+                req.url.path(),
+                req.body
+            ))
+        })
+        .on_response(move |resp| {
+            on_response.lock().unwrap().push(format!(
+                "{} {}",
+                resp.status.as_u16(),
+                String::from_utf8_lossy(resp.body)
+            ))
+        })
+        .build()
+        .unwrap();
+
+    client.employee(&"1000".into()).await.unwrap();
+    let _: serde_json::Value = client
+        .post("daily-workings/timerecord/a", &["token", "1"])
+        .await
+        .unwrap();
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [
+            "GET /v1.0/employees/1000 None",
+            r#"200 {"lastName": "", "firstName": "", "key": "a"}"#,
+            r#"POST /v1.0/daily-workings/timerecord/a Some("[\"[REDACTED]\",\"1\"]")"#,
+            "200 {}",
+        ]
+    );
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn carry_status() {
+    use crate::mock::{json, FakeTransport};
+
+    let transport = FakeTransport::new(|req| match req.url.path() {
+        "/v1.0/unauthorized" => json(
+            401,
+            r#"{"errors": [{"message": "認証に失敗しました", "code": 101}]}"#,
+        ),
+        "/v1.0/not-found" => json(
+            404,
+            r#"{"errors": [{"message": "対象が存在しません", "code": 302}]}"#,
+        ),
+        "/v1.0/ok-with-errors" => json(200, r#"{"errors": [{"message": "?", "code": 1}]}"#),
+        _ => json(500, "<html>Internal Server Error</html>"),
+    });
+    let client = Client::with_transport(transport, "token");
+
+    let res: Result<serde_json::Value> = client.get("unauthorized").await;
+    match res {
+        Err(Error::Api { status, errors }) => {
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+            assert_eq!(errors[0].code, 101);
+        }
+        res => panic!("{:?}", res),
+    }
+
+    let res: Result<serde_json::Value> = client.get("not-found").await;
+    match res {
+        Err(Error::Api { status, errors }) => {
+            assert_eq!(status, StatusCode::NOT_FOUND);
+            assert_eq!(errors[0].message, "対象が存在しません");
+        }
+        res => panic!("{:?}", res),
+    }
+
+    let res: Result<serde_json::Value> = client.get("ok-with-errors").await;
+    assert!(
+        matches!(
+            res,
+            Err(Error::Api {
+                status: StatusCode::OK,
+                ..
+            })
+        ),
+        "{:?}",
+        res
+    );
+
+    let res: Result<serde_json::Value> = client.get("server-error").await;
+    match res {
+        Err(Error::Http { status, body }) => {
+            assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+            assert_eq!(body, "<html>Internal Server Error</html>");
+        }
+        res => panic!("{:?}", res),
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn keep_undecodable_body() {
+    use crate::mock::{json, FakeTransport};
+
+    let transport = FakeTransport::new(|req| match req.url.path() {
+        "/v1.0/maintenance" => json(200, "<html>メンテナンス中</html>"),
+        _ => json(200, &"x".repeat(10_000)),
+    });
+    let client = Client::with_transport(transport, "token");
+
+    let res: Result<serde_json::Value> = client.get("maintenance").await;
+    match res {
+        Err(Error::Decode { body, .. }) => assert_eq!(body, "<html>メンテナンス中</html>"),
+        res => panic!("{:?}", res),
+    }
+
+    let res: Result<serde_json::Value> = client.get("garbage").await;
+    match res {
+        Err(Error::Decode { body, .. }) => {
+            assert_eq!(body.len(), MAX_ERROR_BODY + 3);
+            assert!(body.ends_with("x..."));
+        }
+        res => panic!("{:?}", res),
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn report_decode_path() {
+    use crate::daily_workings::timerecord::Response;
+    use crate::mock::{json, FakeTransport};
+
+    let transport = FakeTransport::new(|_| {
+        json(
+            200,
+            r#"[
+                {"date": "2016-05-01", "dailyWorkings": []},
+                {"date": "2016-05-02", "dailyWorkings": [
+                    {"date": "2016-05-02", "employeeKey": "a", "timeRecord": [
+                        {"time": "2016-05-02T09:00:00+09:00", "code": "1"},
+                        {"time": 1462179600, "code": "2"}
+                    ]}
+                ]}
+            ]"#,
+        )
+    });
+    let client = Client::with_transport(transport, "token");
+
+    let res: Result<Response> = client.get("daily-workings/timerecord").await;
+    match res {
+        Err(e @ Error::Decode { .. }) => {
+            let path = "[1].dailyWorkings[0].timeRecord[1].time";
+            assert!(matches!(&e, Error::Decode { path: Some(p), .. } if p == path));
+            assert!(e.to_string().contains(path), "{}", e);
+        }
+        res => panic!("{:?}", res),
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn decompress_response() {
+    use crate::mock::{MockServer, Response};
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    let server = MockServer::start(|req| {
+        let body = r#"[{"date": "2016-05-01", "dailyWorkings": []}]"#;
+        if !req.header("accept-encoding").unwrap_or("").contains("gzip") {
+            return Response::json(200, body);
+        }
+        let mut gz = GzEncoder::new(vec![], flate2::Compression::default());
+        gz.write_all(body.as_bytes()).unwrap();
+        Response {
+            body: gz.finish().unwrap(),
+            ..Response::json(200, "").header("Content-Encoding", "gzip")
+        }
+    })
+    .await;
+    for &compression in &[true, false] {
+        let client = Client::builder()
+            .access_token("token")
+            .base_url(server.url("/"))
+            .compression(compression)
+            .build()
+            .unwrap();
+        let resp = client.daily_workings().await.unwrap();
+        assert_eq!(resp.0[0].date.to_string(), "2016-05-01");
+    }
+
+    let encodings: Vec<_> = server
+        .requests()
+        .iter()
+        .map(|req| req.header("accept-encoding").map(str::to_string))
+        .collect();
+    assert_eq!(encodings, [Some("gzip, br, deflate".to_string()), None]);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn share_http_client() {
+    use crate::mock::{MockServer, Response};
+
+    let server = MockServer::start(|req| match req.target.as_str() {
+        "/v1.0/slow" => Response::json(200, "{}").delay(Duration::from_millis(300)),
+        _ => Response::json(200, "{}"),
+    })
+    .await;
+    let mut headers = HeaderMap::new();
+    headers.insert("x-app", HeaderValue::from_static("attendance-bot"));
+    let http = reqwest::Client::builder()
+        .default_headers(headers)
+        .timeout(Duration::from_millis(50))
+        .build()
+        .unwrap();
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .http_client(http.clone())
+        .build()
+        .unwrap();
+
+    let _: serde_json::Value = client.get("daily-workings").await.unwrap();
+    let req = &server.requests()[0];
+    assert_eq!(req.header("x-app"), Some("attendance-bot"));
+    assert_eq!(req.header("authorization"), Some("Bearer token"));
+    assert!(req.header("content-type").is_some());
+
+    // the client's own timeout is in charge
+    let res: Result<serde_json::Value> = client.get("slow").await;
+    assert!(matches!(res, Err(Error::Timeout(_))), "{:?}", res);
+
+    // and it was not given kingtime's headers
+    http.get(server.url("/plain")).send().await.unwrap();
+    let req = server.requests().pop().unwrap();
+    assert_eq!(req.target, "/plain");
+    assert_eq!(req.header("authorization"), None);
+    assert_eq!(req.header("x-app"), Some("attendance-bot"));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn reuse_connection() {
+    use crate::mock::{MockServer, Response};
+
+    let server = MockServer::start(|_| Response::json(200, r#"{"key": "abc"}"#)).await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .build()
+        .unwrap();
+
+    for _ in 0..2 {
+        let v: serde_json::Value = client.get("employees/1000").await.unwrap();
+        assert_eq!(v["key"], "abc");
+    }
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(server.connections(), 1);
+
+    let req = &server.requests()[0];
+    assert_eq!(req.header("authorization"), Some("Bearer token"));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn tune_connection_pool() {
+    use crate::mock::{MockServer, Response};
+
+    let server = MockServer::start(|_| Response::json(200, "{}")).await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .pool_idle_timeout(Duration::from_secs(30))
+        .tcp_keepalive(Duration::from_secs(15))
+        .build()
+        .unwrap();
+    for _ in 0..2 {
+        let _: serde_json::Value = client.get("daily-workings").await.unwrap();
+    }
+    assert_eq!(server.connections(), 1);
+
+    // nothing is kept for reuse
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .pool_max_idle_per_host(0)
+        .pool_idle_timeout(None)
+        .tcp_keepalive(None)
+        .build()
+        .unwrap();
+    for _ in 0..2 {
+        let _: serde_json::Value = client.get("daily-workings").await.unwrap();
+    }
+    assert_eq!(server.connections(), 3);
+}
@@ -0,0 +1,182 @@
+//! The functions that took employee keys and codes as `&str`, before
+//! [`EmployeeKey`](crate::EmployeeKey) and
+//! [`EmployeeCode`](crate::EmployeeCode). They are deprecated and will be
+//! removed in the next release; wrap the strings instead:
+//!
+//! ```no_run
+//! # async fn run() -> kingtime::Result<()> {
+//! # let token = "";
+//! let employee = kingtime::employees::get(token, &"1000".into()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::EmployeeKey;
+
+fn to_keys(keys: &[&str]) -> Vec<EmployeeKey> {
+    keys.iter().map(|&key| EmployeeKey::from(key)).collect()
+}
+
+pub mod employees {
+    use crate::employees::{self, AdditionalField, NewKey, Response, UpdateRequest};
+    use crate::{EmployeeCode, EmployeeKey, ResponseEnvelope, Result};
+    use chrono::NaiveDate;
+
+    #[deprecated(note = "use `employees::get` with an `EmployeeCode`")]
+    pub async fn get(access_token: &str, code: &str) -> Result<Response> {
+        employees::get(access_token, &EmployeeCode::from(code)).await
+    }
+
+    #[deprecated(note = "use `employees::get_with` with an `EmployeeCode`")]
+    pub async fn get_with(
+        access_token: &str,
+        code: &str,
+        fields: &[AdditionalField],
+    ) -> Result<Response> {
+        employees::get_with(access_token, &EmployeeCode::from(code), fields).await
+    }
+
+    #[deprecated(note = "use `employees::get_as_of` with an `EmployeeCode`")]
+    pub async fn get_as_of(
+        access_token: &str,
+        code: &str,
+        date: Option<NaiveDate>,
+    ) -> Result<Response> {
+        employees::get_as_of(access_token, &EmployeeCode::from(code), date).await
+    }
+
+    #[deprecated(note = "use `employees::get_with_meta` with an `EmployeeCode`")]
+    pub async fn get_with_meta(
+        access_token: &str,
+        code: &str,
+    ) -> Result<ResponseEnvelope<Response>> {
+        employees::get_with_meta(access_token, &EmployeeCode::from(code)).await
+    }
+
+    #[deprecated(note = "use `employees::update` with an `EmployeeKey`")]
+    pub async fn update(access_token: &str, key: &str, req: &UpdateRequest) -> Result<Response> {
+        employees::update(access_token, &EmployeeKey::from(key), req).await
+    }
+
+    #[deprecated(note = "use `employees::reissue_key` with an `EmployeeKey`")]
+    pub async fn reissue_key(access_token: &str, current_key: &str) -> Result<NewKey> {
+        employees::reissue_key(access_token, &EmployeeKey::from(current_key)).await
+    }
+}
+
+pub mod timerecord {
+    use super::to_keys;
+    use crate::daily_workings::timerecord::{
+        self, DailyWorking, DeleteRequest, Filter, ManyResponse, Request, Response,
+    };
+    use crate::{EmployeeKey, ResponseEnvelope, Result};
+    use chrono::NaiveDate;
+
+    #[deprecated(note = "use `daily_workings::timerecord::post` with an `EmployeeKey`")]
+    pub async fn post(access_token: &str, key: &str, req: &Request) -> Result<()> {
+        timerecord::post(access_token, &EmployeeKey::from(key), req).await
+    }
+
+    #[deprecated(note = "use `daily_workings::timerecord::delete` with an `EmployeeKey`")]
+    pub async fn delete(access_token: &str, key: &str, req: &DeleteRequest) -> Result<()> {
+        timerecord::delete(access_token, &EmployeeKey::from(key), req).await
+    }
+
+    #[deprecated(note = "use `daily_workings::timerecord::get` with `EmployeeKey`s")]
+    pub async fn get(
+        access_token: &str,
+        keys: &[&str],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Response> {
+        timerecord::get(access_token, &to_keys(keys), start, end).await
+    }
+
+    #[deprecated(note = "use `daily_workings::timerecord::get_with` with `EmployeeKey`s")]
+    pub async fn get_with(
+        access_token: &str,
+        keys: &[&str],
+        start: NaiveDate,
+        end: NaiveDate,
+        filter: &Filter,
+    ) -> Result<Response> {
+        timerecord::get_with(access_token, &to_keys(keys), start, end, filter).await
+    }
+
+    #[deprecated(note = "use `daily_workings::timerecord::get_by_date` with `EmployeeKey`s")]
+    pub async fn get_by_date(
+        access_token: &str,
+        keys: &[&str],
+        date: NaiveDate,
+    ) -> Result<Vec<DailyWorking>> {
+        timerecord::get_by_date(access_token, &to_keys(keys), date).await
+    }
+
+    #[deprecated(note = "use `daily_workings::timerecord::get_with_meta` with `EmployeeKey`s")]
+    pub async fn get_with_meta(
+        access_token: &str,
+        keys: &[&str],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<ResponseEnvelope<Response>> {
+        timerecord::get_with_meta(access_token, &to_keys(keys), start, end).await
+    }
+
+    #[deprecated(note = "use `daily_workings::timerecord::get_many` with `EmployeeKey`s")]
+    pub async fn get_many(
+        access_token: &str,
+        keys: &[&str],
+        start: NaiveDate,
+        end: NaiveDate,
+        concurrency: usize,
+    ) -> Result<ManyResponse> {
+        timerecord::get_many(access_token, &to_keys(keys), start, end, concurrency).await
+    }
+}
+
+pub mod daily_schedules {
+    use crate::daily_schedules::{self, ScheduleRequest};
+    use crate::{EmployeeKey, Result};
+    use chrono::NaiveDate;
+
+    #[deprecated(note = "use `daily_schedules::post` with an `EmployeeKey`")]
+    pub async fn post(
+        access_token: &str,
+        date: NaiveDate,
+        key: &str,
+        req: &ScheduleRequest,
+    ) -> Result<()> {
+        daily_schedules::post(access_token, date, &EmployeeKey::from(key), req).await
+    }
+
+    #[deprecated(note = "use `daily_schedules::update` with an `EmployeeKey`")]
+    pub async fn update(
+        access_token: &str,
+        date: NaiveDate,
+        key: &str,
+        req: &ScheduleRequest,
+    ) -> Result<()> {
+        daily_schedules::update(access_token, date, &EmployeeKey::from(key), req).await
+    }
+}
+
+pub mod yearly_holidays {
+    use super::to_keys;
+    use crate::yearly_holidays::{self, Query, RemainingLeave, YearlyHolidays};
+    use crate::{EmployeeKey, Result};
+    use chrono::NaiveDate;
+
+    #[deprecated(note = "use `yearly_holidays::get` with an `EmployeeKey`")]
+    pub async fn get(access_token: &str, key: &str, query: &Query) -> Result<YearlyHolidays> {
+        yearly_holidays::get(access_token, &EmployeeKey::from(key), query).await
+    }
+
+    #[deprecated(note = "use `yearly_holidays::remaining_summary` with `EmployeeKey`s")]
+    pub async fn remaining_summary(
+        access_token: &str,
+        keys: &[&str],
+        as_of: NaiveDate,
+    ) -> Result<Vec<(EmployeeKey, RemainingLeave)>> {
+        yearly_holidays::remaining_summary(access_token, &to_keys(keys), as_of).await
+    }
+}
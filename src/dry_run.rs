@@ -0,0 +1,161 @@
+use crate::token::SecretToken;
+use crate::transport::HttpRequest;
+use http::Method;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// A request that a client in dry-run mode built but didn't send, see
+/// [`Client::dry_run`](crate::Client::dry_run).
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PlannedRequest {
+    pub method: Method,
+    pub url: Url,
+    /// The JSON body, with every occurrence of the access token replaced by
+    /// `[REDACTED]`.
+    pub body_json: Option<Value>,
+}
+
+// The requests planned by a client in dry-run mode and its clones.
+#[derive(Clone, Default)]
+pub(crate) struct DryRun {
+    planned: Arc<Mutex<Vec<PlannedRequest>>>,
+}
+
+impl DryRun {
+    // Logs and returns `req` if it would change anything on the server;
+    // reads are left to the transport.
+    pub(crate) fn plan(
+        &self,
+        req: &HttpRequest,
+        access_token: &SecretToken,
+    ) -> Option<PlannedRequest> {
+        if matches!(req.method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            return None;
+        }
+        let body_json = req
+            .body
+            .as_deref()
+            .and_then(|body| serde_json::from_str(&access_token.redact(body)).ok());
+        let planned = PlannedRequest {
+            method: req.method.clone(),
+            url: req.url.clone(),
+            body_json,
+        };
+        self.planned.lock().unwrap().push(planned.clone());
+        Some(planned)
+    }
+
+    pub(crate) fn planned(&self) -> Vec<PlannedRequest> {
+        self.planned.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn plan_mutating_requests() {
+    use crate::daily_workings::timerecord::{Code, Request};
+    use crate::mock::{json, FakeTransport};
+    use crate::Client;
+    use serde_json::json;
+
+    let transport = FakeTransport::new(|_| {
+        json(
+            200,
+            r#"{"code": "1000", "key": "8b6ee646", "lastName": "勤怠", "firstName": "太郎"}"#,
+        )
+    });
+    let client = Client::with_transport(transport.clone(), "token").dry_run(true);
+    let req = Request::new(
+        "2016-05-01".parse().unwrap(),
+        "2016-05-01T09:00:00+09:00".parse().unwrap(),
+        Code::In,
+    );
+
+    client
+        .post_timerecord(&"8b6ee646".into(), &req)
+        .await
+        .unwrap();
+    client
+        .clone()
+        .post_timerecord(&"c0ffee".into(), &req)
+        .await
+        .unwrap();
+    assert!(transport.requests().is_empty());
+    // reads still go through
+    assert_eq!(
+        client.employee(&"1000".into()).await.unwrap().key,
+        "8b6ee646"
+    );
+    assert_eq!(transport.requests().len(), 1);
+
+    let body = json!({
+        "date": "2016-05-01",
+        "time": "2016-05-01T09:00:00+09:00",
+        "code": "1",
+    });
+    let planned: Vec<_> = client
+        .planned_requests()
+        .into_iter()
+        .map(|planned| (planned.method, planned.url.to_string(), planned.body_json))
+        .collect();
+    assert_eq!(
+        planned,
+        [
+            (
+                Method::POST,
+                "https://api.kingtime.jp/v1.0/daily-workings/timerecord/8b6ee646".to_string(),
+                Some(body.clone()),
+            ),
+            (
+                Method::POST,
+                "https://api.kingtime.jp/v1.0/daily-workings/timerecord/c0ffee".to_string(),
+                Some(body),
+            ),
+        ]
+    );
+
+    let client = client.dry_run(false);
+    client
+        .post_timerecord(&"8b6ee646".into(), &req)
+        .await
+        .unwrap();
+    assert_eq!(transport.requests().len(), 2);
+    assert!(client.planned_requests().is_empty());
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn hold_back_calls_returning_data() {
+    use crate::mock::{json, FakeTransport};
+    use crate::{Client, Error};
+    use serde_json::json;
+
+    let transport = FakeTransport::new(|_| json(200, r#"{"key": "8b6ee646"}"#));
+    let client = Client::with_transport(transport.clone(), "token").dry_run(true);
+
+    // nothing to return, so nothing missing
+    client
+        .delete::<()>("daily-workings/timerecord/8b6ee646")
+        .await
+        .unwrap();
+    let raw: serde_json::Value = client.put("employees/8b6ee646", &json!({})).await.unwrap();
+    assert_eq!(raw, serde_json::Value::Null);
+
+    let e = client
+        .put::<_, std::collections::BTreeMap<String, String>>("employees/8b6ee646", &json!({}))
+        .await
+        .unwrap_err();
+    assert_eq!(e.to_string(), "PUT request held back in dry-run mode");
+    match e {
+        Error::Planned(planned) => {
+            assert_eq!(planned.method, Method::PUT);
+            assert_eq!(planned.url.path(), "/v1.0/employees/8b6ee646");
+            assert_eq!(planned.body_json, Some(json!({})));
+        }
+        e => panic!("{:?}", e),
+    }
+    assert!(transport.requests().is_empty());
+    assert_eq!(client.planned_requests().len(), 3);
+}
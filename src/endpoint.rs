@@ -0,0 +1,351 @@
+// Paths of the API endpoints, relative to the versioned API root. Every
+// module builds its requests from these so that the URL layout lives in one
+// place. Codes and keys are percent-encoded, as they may contain anything.
+
+use crate::{Error, Result, YearMonth};
+use chrono::NaiveDate;
+use std::fmt;
+
+pub(crate) const ADMINISTRATORS: &str = "administrators";
+pub(crate) const COMPANIES: &str = "companies";
+pub(crate) const EMPLOYEES: &str = "employees";
+pub(crate) const DIVISIONS: &str = "divisions";
+pub(crate) const DAILY_WORKINGS: &str = "daily-workings";
+pub(crate) const TIMERECORDS: &str = "daily-workings/timerecord";
+pub(crate) const DAILY_SCHEDULES: &str = "daily-schedules";
+pub(crate) const MONTHLY_WORKINGS: &str = "monthly-workings";
+pub(crate) const MONTHLY_SCHEDULES: &str = "monthly-schedules";
+pub(crate) const OVERTIME_REQUESTS: &str = "requests/overtime";
+pub(crate) const SCHEDULE_REQUESTS: &str = "requests/schedule";
+pub(crate) const HOLIDAY_REQUESTS: &str = "requests/holiday";
+pub(crate) const YEARLY_HOLIDAYS: &str = "yearly-holidays";
+pub(crate) const WORKING_TYPES: &str = "working-types";
+
+pub(crate) fn administrator(admin_key: &str) -> Result<String> {
+    Ok(format!("{}/{}", ADMINISTRATORS, segment(admin_key)?))
+}
+
+pub(crate) fn employee(code: &str) -> Result<String> {
+    Ok(format!("{}/{}", EMPLOYEES, segment(code)?))
+}
+
+pub(crate) fn employee_key(employee_key: &str) -> Result<String> {
+    Ok(format!("{}/{}/key", EMPLOYEES, segment(employee_key)?))
+}
+
+pub(crate) fn division(code: &str) -> Result<String> {
+    Ok(format!("{}/{}", DIVISIONS, segment(code)?))
+}
+
+pub(crate) fn daily_workings_on(date: NaiveDate) -> String {
+    format!("{}/{}", DAILY_WORKINGS, date.format("%Y-%m-%d"))
+}
+
+pub(crate) fn timerecords_on(date: NaiveDate) -> String {
+    format!("{}/{}", TIMERECORDS, date.format("%Y-%m-%d"))
+}
+
+pub(crate) fn daily_schedules_on(date: NaiveDate) -> String {
+    format!("{}/{}", DAILY_SCHEDULES, date.format("%Y-%m-%d"))
+}
+
+pub(crate) fn daily_schedule(employee_key: &str) -> Result<String> {
+    Ok(format!("{}/{}", DAILY_SCHEDULES, segment(employee_key)?))
+}
+
+pub(crate) fn monthly_workings_of(month: YearMonth) -> String {
+    format!("{}/{}", MONTHLY_WORKINGS, month)
+}
+
+pub(crate) fn overtime_request(request_key: &str) -> Result<String> {
+    Ok(format!("{}/{}", OVERTIME_REQUESTS, segment(request_key)?))
+}
+
+pub(crate) fn schedule_request(request_key: &str) -> Result<String> {
+    Ok(format!("{}/{}", SCHEDULE_REQUESTS, segment(request_key)?))
+}
+
+pub(crate) fn holiday_request(request_key: &str) -> Result<String> {
+    Ok(format!("{}/{}", HOLIDAY_REQUESTS, segment(request_key)?))
+}
+
+pub(crate) fn yearly_holidays(employee_key: &str) -> Result<String> {
+    Ok(format!("{}/{}", YEARLY_HOLIDAYS, segment(employee_key)?))
+}
+
+pub(crate) fn working_type(code: &str) -> Result<String> {
+    Ok(format!("{}/{}", WORKING_TYPES, segment(code)?))
+}
+
+pub(crate) fn timerecord(employee_key: &str) -> Result<String> {
+    Ok(format!("{}/{}", TIMERECORDS, segment(employee_key)?))
+}
+
+// Everything but the unreserved characters of RFC 3986 is escaped. Empty
+// and dot segments are refused, as URL resolution would drop them or step
+// up the path, even escaped.
+fn segment(value: &str) -> Result<String> {
+    if matches!(value, "" | "." | "..") {
+        return Err(Error::Builder(format!(
+            "{:?} is not a valid path segment",
+            value
+        )));
+    }
+    let mut encoded = String::with_capacity(value.len());
+    for b in value.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(char::from(b))
+            }
+            b => encoded.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    Ok(encoded)
+}
+
+/// The API endpoint a request was made to, with the codes and keys in its
+/// path left out, e.g. for labelling metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Endpoint {
+    Administrators,
+    Administrator,
+    Companies,
+    Employees,
+    Employee,
+    EmployeeKey,
+    Divisions,
+    Division,
+    DailyWorkings,
+    DailyWorkingsOfDay,
+    TimeRecords,
+    TimeRecordsOfDay,
+    EmployeeTimeRecords,
+    DailySchedules,
+    DailySchedulesOfDay,
+    EmployeeDailySchedule,
+    MonthlyWorkings,
+    MonthlyWorkingsOfMonth,
+    MonthlySchedules,
+    OvertimeRequests,
+    OvertimeRequest,
+    ScheduleRequests,
+    ScheduleRequest,
+    HolidayRequests,
+    HolidayRequest,
+    YearlyHolidays,
+    WorkingTypes,
+    WorkingType,
+    /// A path this crate doesn't know of.
+    Other,
+}
+
+impl Endpoint {
+    // `path` is relative to the versioned API root, like the ones above.
+    pub(crate) fn of(path: &str) -> Self {
+        let segments: Vec<_> = path.trim_matches('/').split('/').collect();
+        match segments[..] {
+            ["administrators"] => Endpoint::Administrators,
+            ["administrators", _] => Endpoint::Administrator,
+            ["companies"] => Endpoint::Companies,
+            ["employees"] => Endpoint::Employees,
+            ["employees", _] => Endpoint::Employee,
+            ["employees", _, "key"] => Endpoint::EmployeeKey,
+            ["divisions"] => Endpoint::Divisions,
+            ["divisions", _] => Endpoint::Division,
+            ["daily-workings"] => Endpoint::DailyWorkings,
+            ["daily-workings", "timerecord"] => Endpoint::TimeRecords,
+            // a date unless it's an employee key
+            ["daily-workings", "timerecord", s] if s.parse::<NaiveDate>().is_ok() => {
+                Endpoint::TimeRecordsOfDay
+            }
+            ["daily-workings", "timerecord", _] => Endpoint::EmployeeTimeRecords,
+            ["daily-workings", _] => Endpoint::DailyWorkingsOfDay,
+            ["daily-schedules"] => Endpoint::DailySchedules,
+            // a date unless it's an employee key
+            ["daily-schedules", s] if s.parse::<NaiveDate>().is_ok() => {
+                Endpoint::DailySchedulesOfDay
+            }
+            ["daily-schedules", _] => Endpoint::EmployeeDailySchedule,
+            ["monthly-workings"] => Endpoint::MonthlyWorkings,
+            ["monthly-workings", _] => Endpoint::MonthlyWorkingsOfMonth,
+            ["monthly-schedules"] => Endpoint::MonthlySchedules,
+            ["requests", "overtime"] => Endpoint::OvertimeRequests,
+            ["requests", "overtime", _] => Endpoint::OvertimeRequest,
+            ["requests", "schedule"] => Endpoint::ScheduleRequests,
+            ["requests", "schedule", _] => Endpoint::ScheduleRequest,
+            ["requests", "holiday"] => Endpoint::HolidayRequests,
+            ["requests", "holiday", _] => Endpoint::HolidayRequest,
+            ["yearly-holidays", _] => Endpoint::YearlyHolidays,
+            ["working-types"] => Endpoint::WorkingTypes,
+            ["working-types", _] => Endpoint::WorkingType,
+            _ => Endpoint::Other,
+        }
+    }
+
+    /// The path template, e.g. `employees/{employeeCode}`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Endpoint::Administrators => "administrators",
+            Endpoint::Administrator => "administrators/{administratorKey}",
+            Endpoint::Companies => "companies",
+            Endpoint::Employees => "employees",
+            Endpoint::Employee => "employees/{employeeCode}",
+            Endpoint::EmployeeKey => "employees/{employeeKey}/key",
+            Endpoint::Divisions => "divisions",
+            Endpoint::Division => "divisions/{divisionCode}",
+            Endpoint::DailyWorkings => "daily-workings",
+            Endpoint::DailyWorkingsOfDay => "daily-workings/{date}",
+            Endpoint::TimeRecords => "daily-workings/timerecord",
+            Endpoint::TimeRecordsOfDay => "daily-workings/timerecord/{date}",
+            Endpoint::EmployeeTimeRecords => "daily-workings/timerecord/{employeeKey}",
+            Endpoint::DailySchedules => "daily-schedules",
+            Endpoint::DailySchedulesOfDay => "daily-schedules/{date}",
+            Endpoint::EmployeeDailySchedule => "daily-schedules/{employeeKey}",
+            Endpoint::MonthlyWorkings => "monthly-workings",
+            Endpoint::MonthlyWorkingsOfMonth => "monthly-workings/{date}",
+            Endpoint::MonthlySchedules => "monthly-schedules",
+            Endpoint::OvertimeRequests => "requests/overtime",
+            Endpoint::OvertimeRequest => "requests/overtime/{requestKey}",
+            Endpoint::ScheduleRequests => "requests/schedule",
+            Endpoint::ScheduleRequest => "requests/schedule/{requestKey}",
+            Endpoint::HolidayRequests => "requests/holiday",
+            Endpoint::HolidayRequest => "requests/holiday/{requestKey}",
+            Endpoint::YearlyHolidays => "yearly-holidays/{employeeKey}",
+            Endpoint::WorkingTypes => "working-types",
+            Endpoint::WorkingType => "working-types/{workingTypeCode}",
+            Endpoint::Other => "other",
+        }
+    }
+
+    // Whether the path must be kept out of logs.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn hides_path(&self) -> bool {
+        matches!(self, Endpoint::EmployeeKey)
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[test]
+fn classify_paths() {
+    assert_eq!(Endpoint::of(ADMINISTRATORS), Endpoint::Administrators);
+    assert_eq!(
+        Endpoint::of(&administrator("4d2a8dbb").unwrap()),
+        Endpoint::Administrator
+    );
+    assert_eq!(Endpoint::of(COMPANIES), Endpoint::Companies);
+    assert_eq!(Endpoint::of(EMPLOYEES), Endpoint::Employees);
+    assert_eq!(Endpoint::of(&employee("1000").unwrap()), Endpoint::Employee);
+    assert_eq!(
+        Endpoint::of(&employee_key("8b6ee646").unwrap()),
+        Endpoint::EmployeeKey
+    );
+    assert_eq!(Endpoint::of(DIVISIONS), Endpoint::Divisions);
+    assert_eq!(
+        Endpoint::of(&division("1000/東京").unwrap()),
+        Endpoint::Division
+    );
+    assert_eq!(Endpoint::of(DAILY_WORKINGS), Endpoint::DailyWorkings);
+    assert_eq!(
+        Endpoint::of(&daily_workings_on(
+            NaiveDate::from_ymd_opt(2016, 5, 1).unwrap()
+        )),
+        Endpoint::DailyWorkingsOfDay
+    );
+    assert_eq!(Endpoint::of(TIMERECORDS), Endpoint::TimeRecords);
+    assert_eq!(
+        Endpoint::of(&timerecords_on(
+            NaiveDate::from_ymd_opt(2016, 5, 1).unwrap()
+        )),
+        Endpoint::TimeRecordsOfDay
+    );
+    assert_eq!(
+        Endpoint::of(&timerecord("8b6ee646").unwrap()),
+        Endpoint::EmployeeTimeRecords
+    );
+    assert_eq!(Endpoint::of(DAILY_SCHEDULES), Endpoint::DailySchedules);
+    assert_eq!(
+        Endpoint::of(&daily_schedules_on(
+            NaiveDate::from_ymd_opt(2016, 5, 1).unwrap()
+        )),
+        Endpoint::DailySchedulesOfDay
+    );
+    assert_eq!(
+        Endpoint::of(&daily_schedule("8b6ee646").unwrap()),
+        Endpoint::EmployeeDailySchedule
+    );
+    assert_eq!(Endpoint::of(MONTHLY_WORKINGS), Endpoint::MonthlyWorkings);
+    assert_eq!(
+        Endpoint::of(&monthly_workings_of(YearMonth::new(2016, 5).unwrap())),
+        Endpoint::MonthlyWorkingsOfMonth
+    );
+    assert_eq!(Endpoint::of(MONTHLY_SCHEDULES), Endpoint::MonthlySchedules);
+    assert_eq!(Endpoint::of(OVERTIME_REQUESTS), Endpoint::OvertimeRequests);
+    assert_eq!(
+        Endpoint::of(&overtime_request("5a6c0e2f").unwrap()),
+        Endpoint::OvertimeRequest
+    );
+    assert_eq!(Endpoint::of(SCHEDULE_REQUESTS), Endpoint::ScheduleRequests);
+    assert_eq!(
+        Endpoint::of(&schedule_request("5a6c0e2f").unwrap()),
+        Endpoint::ScheduleRequest
+    );
+    assert_eq!(Endpoint::of(HOLIDAY_REQUESTS), Endpoint::HolidayRequests);
+    assert_eq!(
+        Endpoint::of(&holiday_request("5a6c0e2f").unwrap()),
+        Endpoint::HolidayRequest
+    );
+    assert_eq!(
+        Endpoint::of(&yearly_holidays("8b6ee646").unwrap()),
+        Endpoint::YearlyHolidays
+    );
+    assert_eq!(Endpoint::of(WORKING_TYPES), Endpoint::WorkingTypes);
+    assert_eq!(
+        Endpoint::of(&working_type("1").unwrap()),
+        Endpoint::WorkingType
+    );
+    assert_eq!(Endpoint::of("employees/1000/groups"), Endpoint::Other);
+}
+
+#[test]
+fn format_months() {
+    let month = |year, month| monthly_workings_of(YearMonth::new(year, month).unwrap());
+    assert_eq!(month(2016, 5), "monthly-workings/2016-05");
+    assert_eq!(month(2016, 12), "monthly-workings/2016-12");
+    assert_eq!(month(2017, 1), "monthly-workings/2017-01");
+}
+
+#[test]
+fn encode_segments() {
+    assert_eq!(employee("1000").unwrap(), "employees/1000");
+    assert_eq!(division("a-b.c_d~e").unwrap(), "divisions/a-b.c_d~e");
+    assert_eq!(
+        division("1000/2 #?").unwrap(),
+        "divisions/1000%2F2%20%23%3F"
+    );
+    assert_eq!(division("本社").unwrap(), "divisions/%E6%9C%AC%E7%A4%BE");
+    assert_eq!(working_type("A/1").unwrap(), "working-types/A%2F1");
+    assert_eq!(division("...").unwrap(), "divisions/...");
+    for value in &["", ".", ".."] {
+        for path in &[
+            administrator(value),
+            employee(value),
+            employee_key(value),
+            division(value),
+            daily_schedule(value),
+            overtime_request(value),
+            schedule_request(value),
+            holiday_request(value),
+            yearly_holidays(value),
+            working_type(value),
+            timerecord(value),
+        ] {
+            assert!(matches!(path, Err(Error::Builder(_))), "{:?}", path);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use http::header::HeaderMap;
+use http::StatusCode;
+use std::time::Duration;
+
+/// A decoded response together with what the server sent along with it, as
+/// returned by the `*_with_meta` calls.
+#[derive(Debug, Clone)]
+pub struct ResponseEnvelope<T> {
+    pub(crate) data: T,
+    pub(crate) status: StatusCode,
+    pub(crate) headers: HeaderMap,
+    pub(crate) elapsed: Duration,
+}
+
+impl<T> ResponseEnvelope<T> {
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    pub fn into_data(self) -> T {
+        self.data
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Time taken by the whole call, retries included.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
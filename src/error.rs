@@ -0,0 +1,620 @@
+use crate::endpoint::Endpoint;
+use crate::retry::Failure;
+use chrono::NaiveDate;
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ErrorResponse {
+    pub errors: Vec<ErrorData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct ErrorData {
+    pub message: String,
+    pub code: u32,
+}
+
+impl ErrorData {
+    pub fn code(&self) -> ErrorCode {
+        ErrorCode::from(self.code)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[cfg(feature = "reqwest")]
+    #[error(transparent)]
+    Reqwest(reqwest::Error),
+    #[cfg(all(feature = "isahc", not(target_arch = "wasm32")))]
+    #[error(transparent)]
+    Isahc(isahc::Error),
+    /// No response arrived in time; the source is the error of the
+    /// underlying HTTP stack.
+    #[error("request timed out")]
+    Timeout(#[source] crate::BoxError),
+    #[error("{status}: {errors:?}")]
+    Api {
+        status: StatusCode,
+        errors: Vec<ErrorData>,
+    },
+    /// The access token was rejected by [`Client::ping`](crate::Client::ping).
+    #[error("access token rejected ({status}): {errors:?}")]
+    Unauthorized {
+        status: StatusCode,
+        errors: Vec<ErrorData>,
+    },
+    /// A response that is neither successful nor in the API's error format,
+    /// e.g. a gateway error page.
+    #[error("unexpected response ({status}): {body}")]
+    Http { status: StatusCode, body: String },
+    #[error("failed to encode the request body: {0}")]
+    Encode(#[source] serde_json::Error),
+    /// The response body could not be parsed into the expected type.
+    #[error("failed to decode the response body{}: {source}", at(.path))]
+    Decode {
+        /// The body as received, truncated to a few kilobytes.
+        body: String,
+        /// Where in the body decoding failed, e.g. `[3].dailyWorkings[0].date`.
+        path: Option<String>,
+        source: serde_json::Error,
+    },
+    #[error("rate limited by the server (retry after {retry_after:?})")]
+    RateLimited { retry_after: Option<Duration> },
+    /// The access token cannot be sent in an `Authorization` header.
+    #[error("invalid access token: {0}")]
+    InvalidToken(String),
+    /// The [`AccessTokenProvider`](crate::AccessTokenProvider) failed.
+    #[error("failed to obtain an access token: {0}")]
+    Token(#[source] crate::BoxError),
+    #[error("{0}")]
+    Builder(String),
+    /// A call returning data was held back by a client in
+    /// [dry-run mode](crate::Client::dry_run), so there is no response to
+    /// return it from. The request is listed by
+    /// [`Client::planned_requests`](crate::Client::planned_requests) all the
+    /// same. The URL is left out of the message, as it may hold a key.
+    #[error("{} request held back in dry-run mode", .0.method)]
+    Planned(Box<crate::PlannedRequest>),
+    /// A [`Cassette`](crate::cassette::Cassette) has no recording of the
+    /// request in replay mode, or failed to read or write one.
+    #[cfg(all(feature = "cassette", not(target_arch = "wasm32")))]
+    #[error("{0}")]
+    Cassette(String),
+    /// Any of the above, returned by the API call described in `context`.
+    #[error("{context}: {source}")]
+    Context {
+        context: ErrorContext,
+        source: Box<Error>,
+    },
+}
+
+/// The call an [`Error::Context`] was returned by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ErrorContext {
+    pub endpoint: Endpoint,
+    /// The employee code or key the call was about, shortened to keep logs
+    /// readable; when there were several, the first one and how many others.
+    pub employee: Option<String>,
+    /// The first and last day asked for.
+    pub dates: Option<(NaiveDate, NaiveDate)>,
+}
+
+const EMPLOYEE_CHARS: usize = 8;
+
+impl ErrorContext {
+    pub(crate) fn new(endpoint: Endpoint) -> Self {
+        ErrorContext {
+            endpoint,
+            employee: None,
+            dates: None,
+        }
+    }
+
+    pub(crate) fn employee(mut self, employee: &str) -> Self {
+        self.employee = Some(match employee.char_indices().nth(EMPLOYEE_CHARS) {
+            Some((end, _)) => format!("{}...", &employee[..end]),
+            None => employee.to_string(),
+        });
+        self
+    }
+
+    pub(crate) fn employees<S: AsRef<str>>(self, employees: &[S]) -> Self {
+        match employees {
+            [] => self,
+            [employee] => self.employee(employee.as_ref()),
+            [employee, others @ ..] => {
+                let mut context = self.employee(employee.as_ref());
+                context.employee = context
+                    .employee
+                    .map(|employee| format!("{} and {} more", employee, others.len()));
+                context
+            }
+        }
+    }
+
+    pub(crate) fn dates(mut self, start: NaiveDate, end: NaiveDate) -> Self {
+        self.dates = Some((start, end));
+        self
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.endpoint.as_str())?;
+        match (&self.employee, self.dates) {
+            (Some(employee), Some((start, end))) => {
+                write!(f, " (employee {}, {} to {})", employee, start, end)
+            }
+            (Some(employee), None) => write!(f, " (employee {})", employee),
+            (None, Some((start, end))) => write!(f, " ({} to {})", start, end),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// A coarse classification of an [`Error`], see [`Error::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// No response arrived within the configured timeout.
+    Timeout,
+    /// The server could not be reached: DNS failure, connection refused...
+    Connect,
+    /// A response arrived but its body could not be read or decoded.
+    Decode,
+    /// The server answered with an error, including rate limiting.
+    Api,
+    /// Anything else, e.g. TLS failures or invalid settings.
+    Other,
+}
+
+impl Error {
+    /// Classifies the error, mainly for deciding whether to retry.
+    ///
+    /// | variant | kind |
+    /// |---|---|
+    /// | `Timeout` | `Timeout` |
+    /// | `Reqwest`, `Isahc` failing to connect | `Connect` |
+    /// | `Decode`, `Reqwest`, `Isahc` failing to read the body | `Decode` |
+    /// | `Api`, `Unauthorized`, `Http`, `RateLimited` | `Api` |
+    /// | everything else | `Other` |
+    pub fn kind(&self) -> ErrorKind {
+        match self.inner() {
+            Error::Timeout(_) => ErrorKind::Timeout,
+            e if e.is_connect() => ErrorKind::Connect,
+            #[cfg(feature = "reqwest")]
+            Error::Reqwest(e) if e.is_decode() || e.is_body() => ErrorKind::Decode,
+            #[cfg(all(feature = "isahc", not(target_arch = "wasm32")))]
+            Error::Isahc(e)
+                if matches!(e.kind(), isahc::error::ErrorKind::InvalidContentEncoding) =>
+            {
+                ErrorKind::Decode
+            }
+            Error::Decode { .. } => ErrorKind::Decode,
+            Error::Api { .. }
+            | Error::Unauthorized { .. }
+            | Error::Http { .. }
+            | Error::RateLimited { .. } => ErrorKind::Api,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    /// The call the error was returned by, for the methods of
+    /// [`Client`](crate::Client) calling an endpoint.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Error::Context { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// The error itself, without the [`Error::Context`] around it. The
+    /// helpers below look through it already.
+    pub fn inner(&self) -> &Error {
+        match self {
+            Error::Context { source, .. } => source.inner(),
+            e => e,
+        }
+    }
+
+    pub(crate) fn with_context(self, context: ErrorContext) -> Error {
+        match self {
+            e @ Error::Context { .. } => e,
+            e => Error::Context {
+                context,
+                source: Box::new(e),
+            },
+        }
+    }
+
+    /// The errors reported by the API, for [`Error::Api`] and
+    /// [`Error::Unauthorized`].
+    pub fn api_errors(&self) -> Option<&[ErrorData]> {
+        match self.inner() {
+            Error::Api { errors, .. } | Error::Unauthorized { errors, .. } => Some(errors),
+            _ => None,
+        }
+    }
+
+    /// The status of the response the error was made from, if any.
+    fn status(&self) -> Option<StatusCode> {
+        match self.inner() {
+            Error::Api { status, .. }
+            | Error::Unauthorized { status, .. }
+            | Error::Http { status, .. } => Some(*status),
+            Error::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            _ => None,
+        }
+    }
+
+    /// Whether the access token was rejected: answered with a 401 or 403, or
+    /// with an error code about the token, the IP address or permissions.
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(
+            self.status(),
+            Some(StatusCode::UNAUTHORIZED) | Some(StatusCode::FORBIDDEN)
+        ) || self.has_code(|code| {
+            matches!(
+                code,
+                ErrorCode::InvalidToken
+                    | ErrorCode::ExpiredToken
+                    | ErrorCode::ForbiddenIpAddress
+                    | ErrorCode::PermissionDenied
+            )
+        })
+    }
+
+    /// Whether the API reported that the requested resource does not exist.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self.inner(), Error::Api { status, .. } if *status == StatusCode::NOT_FOUND)
+            || self.has_code(|code| code == ErrorCode::NotFound)
+    }
+
+    /// Whether the API rejected a record that is already registered.
+    pub fn is_duplicate_record(&self) -> bool {
+        self.has_code(|code| code == ErrorCode::DuplicateRecord)
+    }
+
+    /// Whether the server asked to slow down, with a 429 or the
+    /// corresponding error code.
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+            || self.has_code(|code| code == ErrorCode::TooManyRequests)
+    }
+
+    /// Whether the call is worth retrying as is, by the same rules as the
+    /// default [`RetryPolicy`](crate::RetryPolicy): connection failures,
+    /// timeouts, 5xx and rate limiting.
+    pub fn is_transient(&self) -> bool {
+        match self.status() {
+            Some(status) => Failure::Status(status).is_transient(),
+            None => Failure::Error(self.inner()).is_transient(),
+        }
+    }
+
+    fn has_code(&self, mut f: impl FnMut(ErrorCode) -> bool) -> bool {
+        self.api_errors()
+            .is_some_and(|errors| errors.iter().any(|e| f(e.code())))
+    }
+}
+
+// How the HTTP stacks fail. fetch doesn't tell connection failures apart, so
+// on wasm they all count as lost connections.
+impl Error {
+    // No connection could be established, so nothing was sent.
+    pub(crate) fn is_connect(&self) -> bool {
+        match self.inner() {
+            #[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+            Error::Reqwest(e) => e.is_connect(),
+            #[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+            Error::Timeout(e) => e
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(reqwest::Error::is_connect),
+            #[cfg(all(feature = "isahc", not(target_arch = "wasm32")))]
+            Error::Isahc(e) => matches!(
+                e.kind(),
+                isahc::error::ErrorKind::ConnectionFailed | isahc::error::ErrorKind::NameResolution
+            ),
+            _ => false,
+        }
+    }
+
+    // The connection broke down while the request was under way.
+    pub(crate) fn is_connection_lost(&self) -> bool {
+        match self.inner() {
+            #[cfg(feature = "reqwest")]
+            Error::Reqwest(e) => e.is_request(),
+            #[cfg(all(feature = "isahc", not(target_arch = "wasm32")))]
+            Error::Isahc(e) => matches!(e.kind(), isahc::error::ErrorKind::Io),
+            _ => false,
+        }
+    }
+}
+
+fn at(path: &Option<String>) -> String {
+    match path {
+        Some(path) => format!(" at {}", path),
+        None => String::new(),
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            Error::Timeout(Box::new(e))
+        } else {
+            Error::Reqwest(e)
+        }
+    }
+}
+
+#[cfg(all(feature = "isahc", not(target_arch = "wasm32")))]
+impl From<isahc::Error> for Error {
+    fn from(e: isahc::Error) -> Self {
+        if e.is_timeout() {
+            Error::Timeout(Box::new(e))
+        } else {
+            Error::Isahc(e)
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+macro_rules! error_codes {
+    ($($code:literal => $variant:ident, $meaning:literal;)*) => {
+        /// The `code` of an [`ErrorData`], as listed in the KoT API reference.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $(
+                #[doc = $meaning]
+                $variant,
+            )*
+            /// A code this version of the crate does not know about.
+            Unknown(u32),
+        }
+
+        impl From<u32> for ErrorCode {
+            fn from(code: u32) -> Self {
+                match code {
+                    $($code => ErrorCode::$variant,)*
+                    code => ErrorCode::Unknown(code),
+                }
+            }
+        }
+
+        impl ErrorCode {
+            pub fn as_u32(self) -> u32 {
+                match self {
+                    $(ErrorCode::$variant => $code,)*
+                    ErrorCode::Unknown(code) => code,
+                }
+            }
+        }
+
+        impl fmt::Display for ErrorCode {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    $(ErrorCode::$variant => f.write_str($meaning),)*
+                    ErrorCode::Unknown(code) => write!(f, "unknown error (code {})", code),
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    100 => InternalError, "Internal server error.";
+    101 => InvalidToken, "The access token is invalid.";
+    102 => ExpiredToken, "The access token has expired.";
+    103 => ForbiddenIpAddress, "Requests from this IP address are not allowed.";
+    104 => OutOfServiceHours, "The API is not available at this time of day.";
+    105 => TooManyRequests, "Too many requests.";
+    106 => PermissionDenied, "The access token is not permitted to use this endpoint.";
+    200 => InvalidParameter, "A request parameter is invalid.";
+    201 => MissingParameter, "A required request parameter is missing.";
+    202 => InvalidBody, "The request body is malformed.";
+    302 => NotFound, "The requested resource does not exist.";
+    303 => DuplicateRecord, "The record is already registered.";
+    304 => RequestWithdrawn, "The request has been withdrawn by the applicant.";
+    305 => InsufficientHolidays, "The employee does not have enough holidays left.";
+}
+
+#[test]
+fn map_error_codes() {
+    let table = [
+        (100, ErrorCode::InternalError),
+        (101, ErrorCode::InvalidToken),
+        (102, ErrorCode::ExpiredToken),
+        (103, ErrorCode::ForbiddenIpAddress),
+        (104, ErrorCode::OutOfServiceHours),
+        (105, ErrorCode::TooManyRequests),
+        (106, ErrorCode::PermissionDenied),
+        (200, ErrorCode::InvalidParameter),
+        (201, ErrorCode::MissingParameter),
+        (202, ErrorCode::InvalidBody),
+        (302, ErrorCode::NotFound),
+        (303, ErrorCode::DuplicateRecord),
+        (304, ErrorCode::RequestWithdrawn),
+        (305, ErrorCode::InsufficientHolidays),
+        (999, ErrorCode::Unknown(999)),
+    ];
+    for &(code, variant) in &table {
+        assert_eq!(ErrorCode::from(code), variant);
+        assert_eq!(variant.as_u32(), code);
+    }
+    assert_eq!(
+        ErrorCode::NotFound.to_string(),
+        "The requested resource does not exist."
+    );
+    assert_eq!(
+        ErrorCode::Unknown(999).to_string(),
+        "unknown error (code 999)"
+    );
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn classify_transport_errors() {
+    use crate::mock::{MockServer, Response};
+    use crate::Client;
+
+    let server =
+        MockServer::start(|_| Response::json(200, "{}").delay(Duration::from_millis(300))).await;
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(server.url("/"))
+        .timeout(Duration::from_millis(50))
+        .build()
+        .unwrap();
+    let res: Result<serde_json::Value> = client.get("daily-workings").await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::Timeout);
+
+    // nothing listens on a port that was just released
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let client = Client::builder()
+        .access_token("token")
+        .base_url(format!("http://{}", addr))
+        .system_proxy(false)
+        .build()
+        .unwrap();
+    let res: Result<serde_json::Value> = client.get("daily-workings").await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::Connect);
+}
+
+#[test]
+fn classify_errors() {
+    let decode = serde_json::from_str::<u32>("x").unwrap_err();
+    let table = [
+        (
+            Error::Decode {
+                body: "x".into(),
+                path: None,
+                source: decode,
+            },
+            ErrorKind::Decode,
+        ),
+        (
+            Error::Http {
+                status: StatusCode::BAD_GATEWAY,
+                body: "".into(),
+            },
+            ErrorKind::Api,
+        ),
+        (Error::RateLimited { retry_after: None }, ErrorKind::Api),
+        (Error::Builder("".into()), ErrorKind::Other),
+        (Error::InvalidToken("".into()), ErrorKind::Other),
+    ];
+    for (error, kind) in &table {
+        assert_eq!(error.kind(), *kind, "{:?}", error);
+    }
+}
+
+#[test]
+fn classify_api_errors() {
+    let api = |code| Error::Api {
+        status: StatusCode::BAD_REQUEST,
+        errors: vec![ErrorData {
+            message: "".into(),
+            code,
+        }],
+    };
+    assert!(api(302).is_not_found());
+    assert!(!api(302).is_duplicate_record());
+    assert!(api(303).is_duplicate_record());
+    assert!(!api(200).is_not_found());
+    assert!(!Error::Builder("".into()).is_not_found());
+}
+
+#[test]
+fn classify_error_helpers() {
+    let api = |status: u16, code| Error::Api {
+        status: StatusCode::from_u16(status).unwrap(),
+        errors: vec![ErrorData {
+            message: "".into(),
+            code,
+        }],
+    };
+    let http = |status: u16| Error::Http {
+        status: StatusCode::from_u16(status).unwrap(),
+        body: "<html></html>".into(),
+    };
+    let unauthorized = Error::Unauthorized {
+        status: StatusCode::UNAUTHORIZED,
+        errors: vec![],
+    };
+    let rate_limited = Error::RateLimited { retry_after: None };
+    let builder = Error::Builder("".into());
+
+    // (error, unauthorized, not found, rate limited, transient)
+    let table = [
+        (api(401, 101), true, false, false, false),
+        (api(400, 102), true, false, false, false),
+        (api(403, 103), true, false, false, false),
+        (api(400, 106), true, false, false, false),
+        (api(404, 302), false, true, false, false),
+        (api(400, 302), false, true, false, false),
+        (api(404, 200), false, true, false, false),
+        (api(400, 105), false, false, true, false),
+        (api(429, 105), false, false, true, true),
+        (api(500, 100), false, false, false, true),
+        (api(400, 200), false, false, false, false),
+        (http(502), false, false, false, true),
+        (http(404), false, false, false, false),
+        (unauthorized, true, false, false, false),
+        (rate_limited, false, false, true, true),
+        (builder, false, false, false, false),
+    ];
+    for (error, unauthorized, not_found, rate_limited, transient) in &table {
+        assert_eq!(error.is_unauthorized(), *unauthorized, "{:?}", error);
+        assert_eq!(error.is_not_found(), *not_found, "{:?}", error);
+        assert_eq!(error.is_rate_limited(), *rate_limited, "{:?}", error);
+        assert_eq!(error.is_transient(), *transient, "{:?}", error);
+    }
+
+    assert_eq!(api(400, 302).api_errors().unwrap()[0].code, 302);
+    assert!(http(502).api_errors().is_none());
+}
+
+#[test]
+fn describe_context() {
+    let not_found = || Error::Api {
+        status: StatusCode::NOT_FOUND,
+        errors: vec![ErrorData {
+            message: "対象が存在しません".into(),
+            code: 302,
+        }],
+    };
+    let date = |s: &str| s.parse().unwrap();
+
+    let e = not_found().with_context(ErrorContext::new(Endpoint::Employee).employee("1000"));
+    assert_eq!(
+        e.to_string(),
+        format!(
+            "employees/{{employeeCode}} (employee 1000): {}",
+            not_found()
+        )
+    );
+    assert!(e.is_not_found());
+    assert!(matches!(e.inner(), Error::Api { .. }));
+
+    let context = ErrorContext::new(Endpoint::TimeRecords)
+        .employees(&["8b6ee646a9620b28", "c0ffee", "f00"])
+        .dates(date("2016-05-01"), date("2016-05-31"));
+    let e = not_found()
+        .with_context(context.clone())
+        .with_context(ErrorContext::new(Endpoint::Other));
+    assert_eq!(e.context(), Some(&context));
+    assert!(e.to_string().starts_with(
+        "daily-workings/timerecord (employee 8b6ee646... and 2 more, 2016-05-01 to 2016-05-31): "
+    ));
+}
@@ -0,0 +1,64 @@
+use crate::token::SecretToken;
+use crate::transport::{HttpRequest, HttpResponse};
+use http::header::HeaderMap;
+use http::{Method, StatusCode};
+use std::sync::Arc;
+use url::Url;
+
+/// A request about to be sent, as seen by [`ClientBuilder::on_request`].
+///
+/// [`ClientBuilder::on_request`]: crate::ClientBuilder::on_request
+#[derive(Debug)]
+pub struct RequestInfo<'a> {
+    pub method: &'a Method,
+    pub url: &'a Url,
+    /// The JSON body, with every occurrence of the access token replaced by
+    /// `[REDACTED]`.
+    pub body: Option<String>,
+}
+
+/// A response as received, as seen by [`ClientBuilder::on_response`].
+///
+/// [`ClientBuilder::on_response`]: crate::ClientBuilder::on_response
+#[derive(Debug)]
+pub struct ResponseInfo<'a> {
+    pub status: StatusCode,
+    pub headers: &'a HeaderMap,
+    pub body: &'a [u8],
+}
+
+type RequestHook = dyn Fn(&RequestInfo) + Send + Sync;
+type ResponseHook = dyn Fn(&ResponseInfo) + Send + Sync;
+
+#[derive(Clone, Default)]
+pub(crate) struct Hooks {
+    pub(crate) on_request: Vec<Arc<RequestHook>>,
+    pub(crate) on_response: Vec<Arc<ResponseHook>>,
+}
+
+impl Hooks {
+    pub(crate) fn request(&self, req: &HttpRequest, access_token: &SecretToken) {
+        if self.on_request.is_empty() {
+            return;
+        }
+        let info = RequestInfo {
+            method: &req.method,
+            url: &req.url,
+            body: req.body.as_deref().map(|body| access_token.redact(body)),
+        };
+        for hook in &self.on_request {
+            hook(&info);
+        }
+    }
+
+    pub(crate) fn response(&self, resp: &HttpResponse) {
+        let info = ResponseInfo {
+            status: resp.status,
+            headers: &resp.headers,
+            body: &resp.body,
+        };
+        for hook in &self.on_response {
+            hook(&info);
+        }
+    }
+}
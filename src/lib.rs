@@ -1,6 +1,53 @@
-use reqwest::header::{self, HeaderMap};
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use thiserror::Error;
+use http::StatusCode;
+use serde::Deserialize;
+
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
+#[cfg(all(feature = "cassette", not(target_arch = "wasm32")))]
+pub mod cassette;
+mod client;
+pub mod compat;
+mod dry_run;
+mod endpoint;
+mod envelope;
+mod error;
+mod hooks;
+mod metrics;
+#[cfg(test)]
+mod mock;
+mod rate_limit;
+pub mod raw;
+mod retry;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+mod token;
+mod trace;
+mod transport;
+mod types;
+mod year_month;
+
+pub use client::{
+    Client, ClientBuilder, RequestOptions, DEFAULT_API_VERSION, DEFAULT_BASE_URL,
+    DEFAULT_USER_AGENT,
+};
+pub use dry_run::PlannedRequest;
+pub use endpoint::Endpoint;
+pub use envelope::ResponseEnvelope;
+pub use error::{Error, ErrorCode, ErrorContext, ErrorData, ErrorKind, Result};
+pub use hooks::{RequestInfo, ResponseInfo};
+pub use metrics::{MetricsSink, NoopMetrics, RequestMetrics};
+pub use rate_limit::RateLimitInfo;
+pub use retry::{Failure, RetryPolicy};
+pub use token::{AccessTokenProvider, BoxError, SecretToken};
+#[cfg(all(feature = "isahc", not(target_arch = "wasm32")))]
+pub use transport::IsahcTransport;
+#[cfg(feature = "reqwest")]
+pub use transport::ReqwestTransport;
+pub use transport::{BoxFuture, HttpRequest, HttpResponse, Transport};
+pub use types::{
+    CalculationUnit, CurrentDateEmployee, EmployeeCode, EmployeeGroup, EmployeeKey, Gender, Minutes,
+};
+pub use year_month::YearMonth;
 
 // KoT API only correctly recognizes iso8061 strings with +09:00
 mod ts_seconds_jst {
@@ -12,166 +59,321 @@ mod ts_seconds_jst {
     where
         S: Serializer,
     {
+        jst(value).serialize(serializer)
+    }
+
+    fn jst(value: &DateTime<Utc>) -> String {
         // discard millis
         let str = value.to_rfc3339_opts(SecondsFormat::Secs, false);
         let value: DateTime<Utc> = str.parse().unwrap();
 
-        let jst = FixedOffset::east(9 * 3600);
-        value.with_timezone(&jst).to_rfc3339().serialize(serializer)
+        let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+        value.with_timezone(&jst).to_rfc3339()
+    }
+
+    pub mod option {
+        use chrono::{DateTime, Utc};
+        use serde::ser::Serializer;
+        use serde::Serialize;
+
+        pub fn serialize<S>(value: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            value.as_ref().map(super::jst).serialize(serializer)
+        }
     }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(untagged)]
-enum Response<R> {
-    Error { errors: Vec<ErrorData> },
-    Ok(R),
+// Times of day are written HH:MM by the API
+mod hh_mm {
+    pub mod option {
+        use chrono::NaiveTime;
+        use serde::de::{self, Deserializer};
+        use serde::ser::Serializer;
+        use serde::{Deserialize, Serialize};
+
+        pub fn serialize<S>(value: &Option<NaiveTime>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            value
+                .map(|time| time.format("%H:%M").to_string())
+                .serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<NaiveTime>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            match Option::<String>::deserialize(deserializer)? {
+                Some(s) => NaiveTime::parse_from_str(&s, "%H:%M")
+                    .map(Some)
+                    .map_err(|_| {
+                        de::Error::invalid_value(de::Unexpected::Str(&s), &"an HH:MM time")
+                    }),
+                None => Ok(None),
+            }
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ErrorData {
-    pub message: String,
-    pub code: u32,
+/// Checks that `access_token` is accepted by the API, with one cheap request.
+///
+/// A rejected token fails with an error for which [`Error::is_unauthorized`]
+/// holds.
+pub async fn validate_token(access_token: &str) -> Result<TokenInfo> {
+    Client::builder()
+        .access_token(access_token)
+        .build()?
+        .ping()
+        .await
 }
 
-#[derive(Debug, Error)]
-pub enum Error {
-    #[error(transparent)]
-    Reqwest(#[from] reqwest::Error),
-    #[error("{0:?}")]
-    Api(Vec<ErrorData>),
+/// What [`Client::ping`] learned about the access token.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TokenInfo {
+    /// The company the token belongs to, when reported.
+    pub company_code: Option<String>,
+    pub company_name: Option<String>,
 }
 
-pub type Result<T> = std::result::Result<T, Error>;
-
-async fn get<D: DeserializeOwned>(access_token: &str, api: &str) -> Result<D> {
-    let mut headers = HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        "application/json; charset=utf-8".parse().unwrap(),
-    );
-    headers.insert(
-        header::AUTHORIZATION,
-        format!("Bearer {}", access_token).parse().unwrap(),
-    );
-
-    let resp: Response<D> = reqwest::Client::new()
-        .get(api)
-        .headers(headers)
-        .send()
-        .await?
-        .json()
-        .await?;
-    match resp {
-        Response::Error { errors } => Err(Error::Api(errors)),
-        Response::Ok(data) => Ok(data),
+impl Client {
+    /// Checks that the access token is accepted by the API. A rejected token
+    /// fails with [`Error::Unauthorized`], wrapped in [`Error::Context`].
+    pub async fn ping(&self) -> Result<TokenInfo> {
+        #[derive(Deserialize)]
+        struct Company {
+            #[serde(default)]
+            code: Option<String>,
+            #[serde(default)]
+            name: Option<String>,
+        }
+
+        let companies: Vec<Company> = self.get(endpoint::COMPANIES).await.map_err(|e| {
+            match e {
+                Error::Api { status, errors }
+                    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN =>
+                {
+                    Error::Unauthorized { status, errors }
+                }
+                e => e,
+            }
+            .with_context(ErrorContext::new(Endpoint::Companies))
+        })?;
+        Ok(companies
+            .into_iter()
+            .next()
+            .map(|company| TokenInfo {
+                company_code: company.code,
+                company_name: company.name,
+            })
+            .unwrap_or_default())
     }
 }
 
-async fn get_with_query<D: DeserializeOwned>(
-    access_token: &str,
-    api: &str,
-    query: &impl Serialize,
-) -> Result<D> {
-    let mut headers = HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        "application/json; charset=utf-8".parse().unwrap(),
-    );
-    headers.insert(
-        header::AUTHORIZATION,
-        format!("Bearer {}", access_token).parse().unwrap(),
-    );
-
-    let resp: Response<D> = reqwest::Client::new()
-        .get(api)
-        .headers(headers)
-        .query(query)
-        .send()
-        .await?
-        .json()
-        .await?;
-    match resp {
-        Response::Error { errors } => Err(Error::Api(errors)),
-        Response::Ok(data) => Ok(data),
+#[cfg(test)]
+#[tokio::test]
+async fn ping() {
+    use crate::mock::{json, FakeTransport};
+
+    let transport = FakeTransport::new(|req| {
+        match req.headers[http::header::AUTHORIZATION].to_str().unwrap() {
+            "Bearer valid" => json(200, r#"[{"code": "1000", "name": "勤怠株式会社"}]"#),
+            "Bearer expired" => json(
+                401,
+                r#"{"errors": [{"message": "トークンの有効期限が切れています", "code": 102}]}"#,
+            ),
+            _ => json(
+                403,
+                r#"{"errors": [{"message": "許可されていないIPアドレスです", "code": 103}]}"#,
+            ),
+        }
+    });
+    let client = |token| {
+        Client::builder()
+            .access_token(token)
+            .transport(transport.clone())
+            .build()
+    };
+
+    let info = client("valid").unwrap().ping().await.unwrap();
+    assert_eq!(info.company_code.as_deref(), Some("1000"));
+    assert_eq!(info.company_name.as_deref(), Some("勤怠株式会社"));
+
+    let res = client("expired").unwrap().ping().await;
+    match res.as_ref().map_err(Error::inner) {
+        Err(Error::Unauthorized { status, errors }) => {
+            assert_eq!(*status, StatusCode::UNAUTHORIZED);
+            assert_eq!(errors[0].code(), ErrorCode::ExpiredToken);
+        }
+        res => panic!("{:?}", res),
     }
+    let res = client("elsewhere").unwrap().ping().await;
+    let e = res.unwrap_err();
+    assert!(matches!(e.inner(), Error::Unauthorized { .. }), "{:?}", e);
+    assert!(e.to_string().starts_with("companies: "), "{}", e);
+
+    assert!(matches!(client("in\nvalid"), Err(Error::InvalidToken(_))));
+    assert_eq!(transport.requests().len(), 3);
 }
 
-async fn post<S: Serialize + ?Sized, D: DeserializeOwned>(
-    access_token: &str,
-    api: &str,
-    payload: &S,
-) -> Result<D> {
-    let mut headers = HeaderMap::new();
-    headers.insert(
-        header::CONTENT_TYPE,
-        "application/json; charset=utf-8".parse().unwrap(),
-    );
-    headers.insert(
-        header::AUTHORIZATION,
-        format!("Bearer {}", access_token).parse().unwrap(),
-    );
-
-    let resp: Response<D> = reqwest::Client::new()
-        .post(api)
-        .headers(headers)
-        .json(payload)
-        .send()
-        .await?
-        .json()
-        .await?;
-    match resp {
-        Response::Error { errors } => Err(Error::Api(errors)),
-        Response::Ok(data) => Ok(data),
+pub mod administrators {
+    use super::{endpoint, Client, Endpoint, ErrorContext, Result};
+    use serde::{Deserialize, Serialize};
+
+    /// Every administrator of the company.
+    pub async fn list(access_token: &str) -> Result<Vec<Administrator>> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .administrators()
+            .await
     }
-}
 
-pub mod employees {
-    use super::Result;
-    use serde::Deserialize;
-
-    pub async fn get(access_token: &str, code: &str) -> Result<Response> {
-        crate::get(
-            access_token,
-            &format!("https://api.kingtime.jp/v1.0/employees/{}", code),
-        )
-        .await
+    /// The administrator `admin_key`, e.g. as found in approval requests.
+    pub async fn get(access_token: &str, admin_key: &str) -> Result<Administrator> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .administrator(admin_key)
+            .await
+    }
+
+    impl Client {
+        pub async fn administrators(&self) -> Result<Vec<Administrator>> {
+            self.get(endpoint::ADMINISTRATORS)
+                .await
+                .map_err(|e| e.with_context(ErrorContext::new(Endpoint::Administrators)))
+        }
+
+        pub async fn administrator(&self, admin_key: &str) -> Result<Administrator> {
+            self.get(&endpoint::administrator(admin_key)?)
+                .await
+                .map_err(|e| e.with_context(ErrorContext::new(Endpoint::Administrator)))
+        }
     }
 
-    #[derive(Debug, Deserialize)]
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
     #[serde(rename_all = "camelCase")]
-    pub struct Response {
+    pub struct Administrator {
+        pub key: String,
+        pub code: String,
         pub last_name: String,
         pub first_name: String,
-        pub key: String,
+        pub email_address: Option<String>,
+    }
+
+    #[test]
+    fn deserialize_list() {
+        let ex = r##"
+[
+  {
+    "key": "4d2a8dbb6cc7d8a4e0a3c64d38191b5a",
+    "code": "admin01",
+    "lastName": "管理",
+    "firstName": "一郎",
+    "emailAddress": "kanri.ichiro@example.com"
+  },
+  {
+    "key": "9e107d9d372bb6826bd81d3542a419d6",
+    "code": "admin02",
+    "lastName": "管理",
+    "firstName": "二郎"
+  }
+]
+        "##;
+
+        let admins: Vec<Administrator> = serde_json::from_str(ex).unwrap();
+        assert_eq!(admins.len(), 2);
+        assert_eq!(admins[0].code, "admin01");
+        assert_eq!(
+            admins[0].email_address.as_deref(),
+            Some("kanri.ichiro@example.com")
+        );
+        assert_eq!(admins[1].first_name, "二郎");
+        assert_eq!(admins[1].email_address, None);
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn get_administrator() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|req| match req.url.path() {
+            "/v1.0/administrators/4d2a8dbb" => json(
+                200,
+                r#"{"key": "4d2a8dbb", "code": "admin01", "lastName": "管理", "firstName": "一郎"}"#,
+            ),
+            _ => json(
+                404,
+                r#"{"errors": [{"message": "対象が存在しません", "code": 302}]}"#,
+            ),
+        });
+        let client = Client::with_transport(transport, "token");
+
+        let admin = client.administrator("4d2a8dbb").await.unwrap();
+        assert_eq!((&*admin.last_name, &*admin.first_name), ("管理", "一郎"));
+        let e = client.administrator("9e107d9d").await.unwrap_err();
+        assert!(e.is_not_found(), "{:?}", e);
     }
 }
 
-pub mod daily_workings {
-    use super::Result;
-    use chrono::NaiveDate;
-    use serde::Deserialize;
+pub mod companies {
+    use super::{endpoint, Client, Endpoint, ErrorContext, Result};
+    use chrono::NaiveTime;
+    use serde::{Deserialize, Serialize};
 
-    pub async fn get(access_token: &str) -> Result<Response> {
-        super::get(access_token, "https://api.kingtime.jp/v1.0/daily-workings").await
+    /// The companies the access token gives access to.
+    pub async fn get(access_token: &str) -> Result<Vec<Company>> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .companies()
+            .await
     }
 
-    #[derive(Debug, Deserialize)]
-    pub struct Response(pub Vec<DailyWorkings>);
+    impl Client {
+        pub async fn companies(&self) -> Result<Vec<Company>> {
+            self.get(endpoint::COMPANIES)
+                .await
+                .map_err(|e| e.with_context(ErrorContext::new(Endpoint::Companies)))
+        }
+    }
 
-    #[derive(Debug, Deserialize)]
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
     #[serde(rename_all = "camelCase")]
-    pub struct DailyWorkings {
-        pub date: NaiveDate,
-        pub daily_workings: Vec<DailyWorking>,
+    pub struct Company {
+        pub code: String,
+        pub name: String,
+        /// The host serving the company's web pages, e.g. `s2.kingtime.jp`.
+        pub host: Option<String>,
+        pub settings: Option<Settings>,
+    }
+
+    impl Company {
+        /// When a working day ends and the next one begins, e.g. 05:00 for
+        /// a night shift to count towards the day it started on.
+        pub fn day_border(&self) -> Option<NaiveTime> {
+            self.settings.as_ref()?.day_border_time
+        }
+
+        /// The minutes worked time is rounded to.
+        pub fn rounding_unit(&self) -> Option<u32> {
+            self.settings.as_ref()?.rounding_unit
+        }
     }
 
-    #[derive(Debug, Deserialize)]
+    /// Company-wide settings.
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
     #[serde(rename_all = "camelCase")]
-    pub struct DailyWorking {
-        pub date: NaiveDate,
-        pub employee_key: String,
-        // ...
+    pub struct Settings {
+        #[serde(default, with = "crate::hh_mm::option")]
+        pub day_border_time: Option<NaiveTime>,
+        pub rounding_unit: Option<u32>,
+        /// The settings not typed above, as returned.
+        #[serde(flatten)]
+        pub other: serde_json::Map<String, serde_json::Value>,
     }
 
     #[test]
@@ -179,329 +381,5351 @@ pub mod daily_workings {
         let ex = r##"
 [
   {
-    "date": "2016-05-01",
-    "dailyWorkings": [
-      {
-        "date": "2016-05-01",
-        "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
-        "currentDateEmployee": {
-          "divisionCode": "1000",
-          "divisionName": "本社",
-          "gender": "male",
-          "typeCode": "1",
-          "typeName": "正社員",
-          "code": "1000",
-          "lastName": "勤怠",
-          "firstName": "太郎",
-          "lastNamePhonetics": "キンタイ",
-          "firstNamePhonetics": "タロウ",
-          "employeeGroups": [
-            {
-              "code": "0001",
-              "name": "人事部"
-            },
-            {
-              "code": "0002",
-              "name": "総務部"
-            }
-          ]
-        },
-        "workPlaceDivisionCode": "1000",
-        "workPlaceDivisionName": "本社",
-        "isClosing": true,
-        "isHelp": false,
-        "isError": false,
-        "workdayTypeName": "平日",
-        "assigned": 480,
-        "unassigned": 135,
-        "overtime": 135,
-        "lateNight": 0,
-        "lateNightUnassigned": 0,
-        "lateNightOvertime": 0,
-        "breakTime": 60,
-        "late": 0,
-        "earlyLeave": 0,
-        "totalWork": 615,
-        "holidaysObtained": {
-          "fulltimeHoliday": {
-            "code": 1,
-            "name": "有休"
-          },
-          "halfdayHolidays": [
-            {
-              "typeName": "PM休",
-              "code": 1,
-              "name": "有休"
-            }
-          ],
-          "hourHolidays": [
-            {
-              "start": "2016-05-01T10:00:00+09:00",
-              "end": "2016-05-01T11:00:00+09:00",
-              "minutes": 60,
-              "code": 1,
-              "name": "有休"
-            }
-          ]
-        },
-        "autoBreakOff": 1,
-        "discretionaryVacation": 0,
-        "customDailyWorkings": [
-          {
-            "code": "dCus1",
-            "name": "日別カスタム1",
-            "calculationUnitCode": 1,
-            "calculationResult": 1
-          },
-          {
-            "code": "dCus2",
-            "name": "日別カスタム2",
-            "calculationUnitCode": 2,
-            "calculationResult": 10
-          },
-          {
-            "code": "dCus3",
-            "name": "日別カスタム3",
-            "calculationUnitCode": 4,
-            "calculationResult": 100
-          }
-        ]
-      }
-    ]
+    "code": "1000",
+    "name": "勤怠株式会社",
+    "host": "s2.kingtime.jp",
+    "settings": {
+      "dayBorderTime": "05:00",
+      "roundingUnit": 15,
+      "startOfWeek": "monday"
+    },
+    "contractPlan": "unknown fields are ignored"
+  },
+  {
+    "code": "2000",
+    "name": "勤怠商事"
   }
 ]
         "##;
 
-        let _: Response = serde_json::from_str(ex).unwrap();
+        let companies: Vec<Company> = serde_json::from_str(ex).unwrap();
+        assert_eq!(companies[0].code, "1000");
+        assert_eq!(companies[0].name, "勤怠株式会社");
+        assert_eq!(companies[0].host.as_deref(), Some("s2.kingtime.jp"));
+        assert_eq!(
+            companies[0].day_border(),
+            Some(NaiveTime::from_hms_opt(5, 0, 0).unwrap())
+        );
+        assert_eq!(companies[0].rounding_unit(), Some(15));
+        let settings = companies[0].settings.as_ref().unwrap();
+        assert_eq!(settings.other["startOfWeek"], "monday");
+        assert!(!settings.other.contains_key("roundingUnit"));
+        assert_eq!(
+            serde_json::to_value(settings).unwrap()["dayBorderTime"],
+            "05:00"
+        );
+        assert_eq!(companies[1].day_border(), None);
+        assert_eq!(companies[1].rounding_unit(), None);
     }
+}
 
-    pub mod timerecord {
-        use crate::Result;
-        use chrono::{DateTime, NaiveDate, Utc};
-        use serde::{de::Visitor, Deserialize, Serialize};
+pub mod employees {
+    use super::{endpoint, Client, Endpoint, ErrorContext, ResponseEnvelope, Result};
+    use crate::Gender;
+    pub use crate::{EmployeeCode, EmployeeGroup, EmployeeKey};
+    use chrono::NaiveDate;
+    use http::Method;
+    use serde::{Deserialize, Serialize};
 
-        pub async fn post(access_token: &str, key: &str, req: &Request) -> Result<()> {
-            let PostResponse {} = crate::post(
-                access_token,
-                &format!(
-                    "https://api.kingtime.jp/v1.0/daily-workings/timerecord/{}",
-                    key
-                ),
-                req,
-            )
-            .await?;
-            Ok(())
-        }
+    pub async fn get(access_token: &str, code: &EmployeeCode) -> Result<Response> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .employee(code)
+            .await
+    }
 
-        #[derive(Serialize)]
-        #[serde(rename_all = "camelCase")]
-        pub struct Request {
-            pub date: NaiveDate,
-            #[serde(with = "crate::ts_seconds_jst")]
-            pub time: DateTime<Utc>,
-            pub code: Code,
-        }
+    /// Like [`get`], also asking for the `fields` that are only returned on
+    /// request.
+    pub async fn get_with(
+        access_token: &str,
+        code: &EmployeeCode,
+        fields: &[AdditionalField],
+    ) -> Result<Response> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .employee_with(code, fields)
+            .await
+    }
 
-        #[test]
-        fn serialize_request() {
-            let req = Request {
-                date: "2016-05-01".parse().unwrap(),
-                time: "2016-05-01T09:00:00+09:00".parse().unwrap(),
-                code: Code::BreakEnd,
-            };
+    /// Like [`get`], with the attributes the employee had on `date`, e.g. the
+    /// division they were in then. `None` is the same as [`get`].
+    pub async fn get_as_of(
+        access_token: &str,
+        code: &EmployeeCode,
+        date: Option<NaiveDate>,
+    ) -> Result<Response> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .employee_as_of(code, date)
+            .await
+    }
 
-            let json = r##"
-            {
-                "date": "2016-05-01",
-                "time": "2016-05-01T09:00:00+09:00",
-                "code": "4"
-            }
-            "##;
+    pub async fn get_with_meta(
+        access_token: &str,
+        code: &EmployeeCode,
+    ) -> Result<ResponseEnvelope<Response>> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .employee_with_meta(code)
+            .await
+    }
 
-            let v1 = serde_json::from_str::<serde_json::Value>(json).unwrap();
-            let v2 =
-                serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&req).unwrap())
-                    .unwrap();
+    /// Every employee of the company, narrowed down by `params`.
+    pub async fn list(access_token: &str, params: &ListParams) -> Result<Vec<Employee>> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .employees(params)
+            .await
+    }
 
-            assert_eq!(v1, v2);
-        }
+    /// Registers a new employee, returning the key it was given.
+    pub async fn create(access_token: &str, req: &CreateRequest) -> Result<CreateResponse> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .create_employee(req)
+            .await
+    }
 
-        #[derive(Deserialize)]
-        struct PostResponse {}
+    /// Changes the attributes of the employee `key` set in `req`, returning
+    /// the employee as updated.
+    pub async fn update(
+        access_token: &str,
+        key: &EmployeeKey,
+        req: &UpdateRequest,
+    ) -> Result<Response> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .update_employee(key, req)
+            .await
+    }
 
-        pub async fn get(
-            access_token: &str,
-            keys: &[&str],
-            start: NaiveDate,
-            end: NaiveDate,
-        ) -> Result<Response> {
-            crate::get_with_query(
-                access_token,
-                "https://api.kingtime.jp/v1.0/daily-workings/timerecord",
-                &[
-                    ("employeeKeys", &*keys.join(",")),
-                    ("start", &start.to_string()),
-                    ("end", &end.to_string()),
-                ],
+    /// Replaces the key of the employee `current_key`, e.g. after it leaked,
+    /// returning the new one. The old one stops working right away.
+    pub async fn reissue_key(access_token: &str, current_key: &EmployeeKey) -> Result<NewKey> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .reissue_employee_key(current_key)
+            .await
+    }
+
+    impl Client {
+        /// Replaces the key of the employee `current_key`, which is kept out
+        /// of the `tracing` spans and error context of the call.
+        pub async fn reissue_employee_key(&self, current_key: &EmployeeKey) -> Result<NewKey> {
+            self.request(
+                Method::PUT,
+                &endpoint::employee_key(current_key.as_str())?,
+                None::<&()>,
+                None::<&()>,
             )
             .await
+            .map_err(|e| e.with_context(ErrorContext::new(Endpoint::EmployeeKey)))
         }
 
-        #[derive(Debug, Deserialize)]
-        pub struct Response(pub Vec<DailyWorkings>);
-
-        #[derive(Debug, Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        pub struct DailyWorkings {
-            pub date: NaiveDate,
-            pub daily_workings: Vec<DailyWorking>,
+        pub async fn update_employee(
+            &self,
+            key: &EmployeeKey,
+            req: &UpdateRequest,
+        ) -> Result<Response> {
+            self.put(&endpoint::employee(key.as_str())?, req)
+                .await
+                .map_err(|e| e.with_context(context(key.as_str())))
         }
 
-        #[derive(Debug, Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        pub struct DailyWorking {
-            pub date: NaiveDate,
-            pub employee_key: String,
-            pub time_record: Vec<TimeRecord>,
+        pub async fn create_employee(&self, req: &CreateRequest) -> Result<CreateResponse> {
+            self.post(endpoint::EMPLOYEES, req).await.map_err(|e| {
+                e.with_context(ErrorContext::new(Endpoint::Employees).employee(req.code.as_str()))
+            })
         }
 
-        #[derive(Debug, Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        pub struct TimeRecord {
-            pub time: DateTime<Utc>,
-            pub code: Code,
+        pub async fn employees(&self, params: &ListParams) -> Result<Vec<Employee>> {
+            self.get_with_query(endpoint::EMPLOYEES, params)
+                .await
+                .map_err(|e| e.with_context(ErrorContext::new(Endpoint::Employees)))
         }
 
-        #[derive(Debug, Clone, Copy)]
-        pub enum Code {
-            In,
-            Out,
-            BreakStart,
-            BreakEnd,
+        pub async fn employee(&self, code: &EmployeeCode) -> Result<Response> {
+            self.employee_with(code, &[]).await
         }
 
-        struct CodeVisitor;
+        /// Like [`Client::employee`], also asking for the `fields` that are
+        /// only returned on request.
+        pub async fn employee_with(
+            &self,
+            code: &EmployeeCode,
+            fields: &[AdditionalField],
+        ) -> Result<Response> {
+            self.employee_query(code, &Query::new(fields)).await
+        }
 
-        impl<'de> Visitor<'de> for CodeVisitor {
-            type Value = Code;
+        /// Like [`Client::employee`], with the attributes the employee had
+        /// on `date`.
+        pub async fn employee_as_of(
+            &self,
+            code: &EmployeeCode,
+            date: Option<NaiveDate>,
+        ) -> Result<Response> {
+            let query = Query {
+                date,
+                ..Query::new(&[])
+            };
+            self.employee_query(code, &query).await
+        }
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                formatter.write_str("code must be an str")
-            }
+        async fn employee_query(&self, code: &EmployeeCode, query: &Query) -> Result<Response> {
+            self.get_with_query(&endpoint::employee(code.as_str())?, query)
+                .await
+                .map_err(|e| e.with_context(context(code.as_str())))
+        }
 
-            fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
-            where
+        /// Like [`Client::employee`], also returning the status, headers and
+        /// timing of the response.
+        pub async fn employee_with_meta(
+            &self,
+            code: &EmployeeCode,
+        ) -> Result<ResponseEnvelope<Response>> {
+            self.request_with_meta(
+                Method::GET,
+                &endpoint::employee(code.as_str())?,
+                None::<&()>,
+                None::<&()>,
+            )
+            .await
+            .map_err(|e| e.with_context(context(code.as_str())))
+        }
+    }
+
+    fn context(code: &str) -> ErrorContext {
+        ErrorContext::new(Endpoint::Employee).employee(code)
+    }
+
+    /// A field of [`Response`] left out unless asked for with
+    /// [`Client::employee_with`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum AdditionalField {
+        EmailAddresses,
+        HiredDate,
+        EnteredDate,
+    }
+
+    impl AdditionalField {
+        /// The name of the field in the API.
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                AdditionalField::EmailAddresses => "emailAddresses",
+                AdditionalField::HiredDate => "hiredDate",
+                AdditionalField::EnteredDate => "enteredDate",
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Query {
+        // comma separated
+        #[serde(skip_serializing_if = "Option::is_none")]
+        additional_fields: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        date: Option<NaiveDate>,
+    }
+
+    impl Query {
+        fn new(fields: &[AdditionalField]) -> Self {
+            let fields: Vec<_> = fields.iter().map(AdditionalField::as_str).collect();
+            Query {
+                additional_fields: Some(fields.join(",")).filter(|fields| !fields.is_empty()),
+                date: None,
+            }
+        }
+    }
+
+    /// An employee as returned by [`get`]. The fields other than the names
+    /// and key are optional to keep accepting trimmed down test fixtures.
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Response {
+        pub last_name: String,
+        pub first_name: String,
+        pub key: EmployeeKey,
+        pub code: Option<EmployeeCode>,
+        pub last_name_phonetics: Option<String>,
+        pub first_name_phonetics: Option<String>,
+        pub division_code: Option<String>,
+        pub division_name: Option<String>,
+        pub type_code: Option<String>,
+        pub type_name: Option<String>,
+        pub gender: Option<Gender>,
+        #[serde(default)]
+        pub employee_groups: Vec<EmployeeGroup>,
+        /// Only with [`AdditionalField::EmailAddresses`].
+        pub email_addresses: Option<Vec<String>>,
+        /// Only with [`AdditionalField::HiredDate`].
+        pub hired_date: Option<NaiveDate>,
+        /// Only with [`AdditionalField::EnteredDate`].
+        pub entered_date: Option<NaiveDate>,
+        /// The last day of an employee who has resigned.
+        pub resignation_date: Option<NaiveDate>,
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let ex = r##"
+{
+  "divisionCode": "1000",
+  "divisionName": "本社",
+  "gender": "male",
+  "typeCode": "1",
+  "typeName": "正社員",
+  "code": "1000",
+  "key": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+  "lastName": "勤怠",
+  "firstName": "太郎",
+  "lastNamePhonetics": "キンタイ",
+  "firstNamePhonetics": "タロウ",
+  "employeeGroups": [
+    {
+      "code": "0001",
+      "name": "人事部"
+    },
+    {
+      "code": "0002",
+      "name": "総務部"
+    }
+  ]
+}
+        "##;
+        let resp: Response = serde_json::from_str(ex).unwrap();
+        assert_eq!(resp.code, Some("1000".into()));
+        assert_eq!(resp.last_name_phonetics.as_deref(), Some("キンタイ"));
+        assert_eq!(resp.first_name_phonetics.as_deref(), Some("タロウ"));
+        assert_eq!(resp.division_code.as_deref(), Some("1000"));
+        assert_eq!(resp.division_name.as_deref(), Some("本社"));
+        assert_eq!(resp.type_code.as_deref(), Some("1"));
+        assert_eq!(resp.type_name.as_deref(), Some("正社員"));
+        assert_eq!(resp.gender, Some(Gender::Male));
+        let groups: Vec<_> = resp.employee_groups.iter().map(|g| &*g.name).collect();
+        assert_eq!(groups, ["人事部", "総務部"]);
+
+        let resp: Response =
+            serde_json::from_str(r#"{"lastName": "勤怠", "firstName": "太郎", "key": "8b6ee646"}"#)
+                .unwrap();
+        assert_eq!(resp.code, None);
+        assert!(resp.employee_groups.is_empty());
+    }
+
+    #[test]
+    fn deserialize_additional_fields() {
+        let ex = r##"
+{
+  "code": "1000",
+  "key": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+  "lastName": "勤怠",
+  "firstName": "太郎",
+  "emailAddresses": [
+    "kintai.taro@example.com",
+    "taro@example.com"
+  ],
+  "hiredDate": "2016-04-01",
+  "enteredDate": "2016-04-05"
+}
+        "##;
+        let resp: Response = serde_json::from_str(ex).unwrap();
+        assert_eq!(
+            resp.email_addresses.unwrap(),
+            ["kintai.taro@example.com", "taro@example.com"]
+        );
+        assert_eq!(resp.hired_date, "2016-04-01".parse().ok());
+        assert_eq!(resp.entered_date, "2016-04-05".parse().ok());
+
+        let ex = r##"
+{
+  "code": "1000",
+  "key": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+  "lastName": "勤怠",
+  "firstName": "太郎"
+}
+        "##;
+        let resp: Response = serde_json::from_str(ex).unwrap();
+        assert_eq!(resp.email_addresses, None);
+        assert_eq!(resp.hired_date, None);
+        assert_eq!(resp.entered_date, None);
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn ask_for_additional_fields() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|_| {
+            json(
+                200,
+                r#"{"code": "1000", "key": "8b6ee646", "lastName": "勤怠", "firstName": "太郎"}"#,
+            )
+        });
+        let client = Client::with_transport(transport.clone(), "token");
+        client.employee(&"1000".into()).await.unwrap();
+        client
+            .employee_with(
+                &"1000".into(),
+                &[AdditionalField::EmailAddresses, AdditionalField::HiredDate],
+            )
+            .await
+            .unwrap();
+
+        let urls: Vec<_> = transport
+            .requests()
+            .iter()
+            .map(|req| req.url.to_string())
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://api.kingtime.jp/v1.0/employees/1000",
+                "https://api.kingtime.jp/v1.0/employees/1000?additionalFields=emailAddresses%2ChiredDate",
+            ]
+        );
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn get_as_of_date() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|_| {
+            json(
+                200,
+                r#"{"code": "1000", "key": "8b6ee646", "lastName": "勤怠", "firstName": "太郎"}"#,
+            )
+        });
+        let client = Client::with_transport(transport.clone(), "token");
+        client
+            .employee_as_of(&"1000".into(), "2023-04-01".parse().ok())
+            .await
+            .unwrap();
+        client.employee_as_of(&"1000".into(), None).await.unwrap();
+
+        let urls: Vec<_> = transport
+            .requests()
+            .iter()
+            .map(|req| req.url.to_string())
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://api.kingtime.jp/v1.0/employees/1000?date=2023-04-01",
+                "https://api.kingtime.jp/v1.0/employees/1000",
+            ]
+        );
+    }
+
+    /// Query of [`list`]. By default every employee currently enrolled is
+    /// listed.
+    #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ListParams {
+        /// Only the employees of the division with this code.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub division: Option<String>,
+        /// Also list the employees who have resigned.
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        pub include_resigner: bool,
+    }
+
+    /// An employee of the company, as listed by [`list`].
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Employee {
+        pub code: EmployeeCode,
+        pub key: EmployeeKey,
+        pub last_name: String,
+        pub first_name: String,
+        pub last_name_phonetics: Option<String>,
+        pub first_name_phonetics: Option<String>,
+        pub division_code: Option<String>,
+        pub division_name: Option<String>,
+        pub type_code: Option<String>,
+        pub type_name: Option<String>,
+        pub gender: Option<Gender>,
+        #[serde(default)]
+        pub employee_groups: Vec<EmployeeGroup>,
+        /// The last day of an employee who has resigned, only listed with
+        /// [`ListParams::include_resigner`].
+        pub resignation_date: Option<NaiveDate>,
+    }
+
+    /// A new employee, for [`create`].
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CreateRequest {
+        pub code: EmployeeCode,
+        pub last_name: String,
+        pub first_name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub last_name_phonetics: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub first_name_phonetics: Option<String>,
+        pub division_code: String,
+        pub type_code: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub hired_date: Option<NaiveDate>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub gender: Option<Gender>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub email_addresses: Vec<String>,
+    }
+
+    /// Changes to an employee, for [`update`]. The fields left to `None` are
+    /// not sent, and stay as they are.
+    #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct UpdateRequest {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub code: Option<EmployeeCode>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub last_name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub first_name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub last_name_phonetics: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub first_name_phonetics: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub division_code: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub type_code: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub hired_date: Option<NaiveDate>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub gender: Option<Gender>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub email_addresses: Option<Vec<String>>,
+    }
+
+    #[test]
+    fn serialize_update_request() {
+        let req = UpdateRequest {
+            code: Some("1001".into()),
+            division_code: Some("2000".into()),
+            first_name: Some("太朗".into()),
+            ..Default::default()
+        };
+        let json = r##"
+        {
+            "code": "1001",
+            "firstName": "太朗",
+            "divisionCode": "2000"
+        }
+        "##;
+        let v1 = serde_json::from_str::<serde_json::Value>(json).unwrap();
+        assert_eq!(v1, serde_json::to_value(&req).unwrap());
+        assert_eq!(
+            serde_json::to_string(&UpdateRequest::default()).unwrap(),
+            "{}"
+        );
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn update_employee() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|req| match req.url.path() {
+            "/v1.0/employees/8b6ee646" => json(
+                200,
+                r#"{"code": "1000", "key": "8b6ee646", "lastName": "勤怠", "firstName": "太郎", "divisionCode": "2000"}"#,
+            ),
+            _ => json(
+                400,
+                r#"{"errors": [{"message": "所属コードが不正です", "code": 200}]}"#,
+            ),
+        });
+        let client = Client::with_transport(transport.clone(), "token");
+        let req = UpdateRequest {
+            division_code: Some("2000".into()),
+            ..Default::default()
+        };
+
+        let resp = client
+            .update_employee(&"8b6ee646".into(), &req)
+            .await
+            .unwrap();
+        assert_eq!(resp.division_code.as_deref(), Some("2000"));
+        let sent = &transport.requests()[0];
+        assert_eq!(sent.method, Method::PUT);
+        assert_eq!(
+            sent.body.as_deref(),
+            Some(&br#"{"divisionCode":"2000"}"#[..])
+        );
+
+        let e = client
+            .update_employee(&"c0ffee".into(), &req)
+            .await
+            .unwrap_err();
+        assert!(matches!(e.inner(), crate::Error::Api { .. }), "{:?}", e);
+        assert_eq!(e.api_errors().unwrap()[0].message, "所属コードが不正です");
+
+        // the updated employee is only known to the server
+        let dry_run = client.dry_run(true);
+        let e = dry_run
+            .update_employee(&"8b6ee646".into(), &req)
+            .await
+            .unwrap_err();
+        assert!(matches!(e.inner(), crate::Error::Planned(_)), "{:?}", e);
+        assert_eq!(transport.requests().len(), 2);
+        let planned = &dry_run.planned_requests()[0];
+        assert_eq!(planned.method, Method::PUT);
+        assert_eq!(
+            planned.body_json,
+            Some(serde_json::json!({"divisionCode": "2000"}))
+        );
+    }
+
+    /// The key given to an employee by [`reissue_key`].
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    pub struct NewKey {
+        pub key: EmployeeKey,
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn reissue_employee_key() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|_| json(200, r#"{"key": "c0ffee"}"#));
+        let client = Client::with_transport(transport.clone(), "token");
+        let new = client
+            .reissue_employee_key(&"8b6ee646".into())
+            .await
+            .unwrap();
+        assert_eq!(new.key, "c0ffee");
+
+        let req = &transport.requests()[0];
+        assert_eq!(req.method, Method::PUT);
+        assert_eq!(
+            req.url.as_str(),
+            "https://api.kingtime.jp/v1.0/employees/8b6ee646/key"
+        );
+
+        // a made-up key would be worse than none
+        let dry_run = client.dry_run(true);
+        let e = dry_run
+            .reissue_employee_key(&"8b6ee646".into())
+            .await
+            .unwrap_err();
+        assert!(matches!(e.inner(), crate::Error::Planned(_)), "{:?}", e);
+        assert_eq!(transport.requests().len(), 1);
+        assert_eq!(
+            dry_run.planned_requests()[0].url.path(),
+            "/v1.0/employees/8b6ee646/key"
+        );
+    }
+
+    /// The employee registered by [`create`].
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CreateResponse {
+        pub code: Option<EmployeeCode>,
+        /// The key generated for the employee, used by the other endpoints.
+        pub key: EmployeeKey,
+    }
+
+    #[test]
+    fn serialize_create_request() {
+        let req = CreateRequest {
+            code: "1000".into(),
+            last_name: "勤怠".into(),
+            first_name: "太郎".into(),
+            last_name_phonetics: Some("キンタイ".into()),
+            first_name_phonetics: Some("タロウ".into()),
+            division_code: "1000".into(),
+            type_code: "1".into(),
+            hired_date: "2016-04-01".parse().ok(),
+            gender: Some(Gender::Male),
+            email_addresses: vec!["kintai.taro@example.com".into()],
+        };
+
+        let json = r##"
+        {
+            "code": "1000",
+            "lastName": "勤怠",
+            "firstName": "太郎",
+            "lastNamePhonetics": "キンタイ",
+            "firstNamePhonetics": "タロウ",
+            "divisionCode": "1000",
+            "typeCode": "1",
+            "hiredDate": "2016-04-01",
+            "gender": "male",
+            "emailAddresses": ["kintai.taro@example.com"]
+        }
+        "##;
+
+        let v1 = serde_json::from_str::<serde_json::Value>(json).unwrap();
+        let v2 = serde_json::to_value(&req).unwrap();
+        assert_eq!(v1, v2);
+
+        let req = CreateRequest {
+            last_name_phonetics: None,
+            first_name_phonetics: None,
+            hired_date: None,
+            gender: None,
+            email_addresses: vec![],
+            ..req
+        };
+        let json = r##"
+        {
+            "code": "1000",
+            "lastName": "勤怠",
+            "firstName": "太郎",
+            "divisionCode": "1000",
+            "typeCode": "1"
+        }
+        "##;
+        let v1 = serde_json::from_str::<serde_json::Value>(json).unwrap();
+        assert_eq!(v1, serde_json::to_value(&req).unwrap());
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn create_employee() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|_| {
+            json(
+                201,
+                r#"{"code": "1000", "key": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3"}"#,
+            )
+        });
+        let client = Client::with_transport(transport.clone(), "token");
+        let req = CreateRequest {
+            code: "1000".into(),
+            last_name: "勤怠".into(),
+            first_name: "太郎".into(),
+            last_name_phonetics: None,
+            first_name_phonetics: None,
+            division_code: "1000".into(),
+            type_code: "1".into(),
+            hired_date: None,
+            gender: None,
+            email_addresses: vec![],
+        };
+        let resp = client.create_employee(&req).await.unwrap();
+        assert_eq!(
+            resp.key,
+            "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3"
+        );
+
+        let sent = &transport.requests()[0];
+        assert_eq!(sent.method, Method::POST);
+        assert_eq!(sent.url.as_str(), "https://api.kingtime.jp/v1.0/employees");
+
+        // no key to return without registering the employee
+        let dry_run = client.dry_run(true);
+        let e = dry_run.create_employee(&req).await.unwrap_err();
+        assert!(matches!(e.inner(), crate::Error::Planned(_)), "{:?}", e);
+        assert_eq!(transport.requests().len(), 1);
+        assert_eq!(dry_run.planned_requests()[0].method, Method::POST);
+    }
+
+    #[test]
+    fn deserialize_list() {
+        let ex = r##"
+[
+  {
+    "divisionCode": "1000",
+    "divisionName": "本社",
+    "gender": "male",
+    "typeCode": "1",
+    "typeName": "正社員",
+    "code": "1000",
+    "key": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+    "lastName": "勤怠",
+    "firstName": "太郎",
+    "lastNamePhonetics": "キンタイ",
+    "firstNamePhonetics": "タロウ",
+    "employeeGroups": [
+      {
+        "code": "0001",
+        "name": "人事部"
+      },
+      {
+        "code": "0002",
+        "name": "総務部"
+      }
+    ]
+  },
+  {
+    "divisionCode": "2000",
+    "divisionName": "大阪支社",
+    "gender": "female",
+    "typeCode": "2",
+    "typeName": "アルバイト",
+    "code": "2000",
+    "key": "c0ffee3bd9b27e0deb9f294a03e3a1b0e5c8ea8fa8372ce72f1a3ca1c3f7e9d2",
+    "lastName": "勤怠",
+    "firstName": "花子",
+    "lastNamePhonetics": "キンタイ",
+    "firstNamePhonetics": "ハナコ",
+    "employeeGroups": []
+  },
+  {
+    "code": "3000",
+    "key": "d15ea5e0",
+    "lastName": "勤怠",
+    "firstName": "次郎"
+  },
+  {
+    "divisionCode": "1000",
+    "divisionName": "本社",
+    "code": "4000",
+    "key": "dec0ded0",
+    "lastName": "勤怠",
+    "firstName": "三郎",
+    "resignationDate": "2016-05-15"
+  }
+]
+        "##;
+
+        let employees: Vec<Employee> = serde_json::from_str(ex).unwrap();
+        assert_eq!(employees.len(), 4);
+        assert_eq!(employees[0].division_name.as_deref(), Some("本社"));
+        assert_eq!(
+            employees[0].employee_groups,
+            [
+                EmployeeGroup {
+                    code: "0001".into(),
+                    name: "人事部".into()
+                },
+                EmployeeGroup {
+                    code: "0002".into(),
+                    name: "総務部".into()
+                },
+            ]
+        );
+        assert_eq!(employees[1].type_name.as_deref(), Some("アルバイト"));
+        assert_eq!(employees[1].first_name_phonetics.as_deref(), Some("ハナコ"));
+        assert_eq!(employees[2].division_code, None);
+        assert!(employees[2].employee_groups.is_empty());
+        assert_eq!(employees[2].resignation_date, None);
+        assert_eq!(
+            employees[3].resignation_date,
+            Some("2016-05-15".parse().unwrap())
+        );
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn list_with_params() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|_| json(200, "[]"));
+        let client = Client::with_transport(transport.clone(), "token");
+        client.employees(&ListParams::default()).await.unwrap();
+        client
+            .employees(&ListParams {
+                division: Some("1000".into()),
+                include_resigner: true,
+            })
+            .await
+            .unwrap();
+
+        let urls: Vec<_> = transport
+            .requests()
+            .iter()
+            .map(|req| req.url.to_string())
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://api.kingtime.jp/v1.0/employees",
+                "https://api.kingtime.jp/v1.0/employees?division=1000&includeResigner=true",
+            ]
+        );
+    }
+}
+
+pub mod divisions {
+    use super::{endpoint, Client, Endpoint, ErrorContext, Result};
+    use chrono::NaiveTime;
+    use serde::de::IgnoredAny;
+    use serde::{Deserialize, Serialize};
+
+    /// The code identifying a division (所属), as found in `divisionCode`
+    /// fields.
+    pub type DivisionCode = String;
+
+    /// Every division of the company.
+    pub async fn list(access_token: &str) -> Result<Vec<Division>> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .divisions()
+            .await
+    }
+
+    /// Adds a division. A code already in use fails with
+    /// [`ErrorCode::DuplicateRecord`](crate::ErrorCode::DuplicateRecord).
+    pub async fn create(access_token: &str, req: &CreateDivision) -> Result<()> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .create_division(req)
+            .await
+    }
+
+    /// Changes the settings of the division `code` set in `req`.
+    pub async fn update(access_token: &str, code: &str, req: &UpdateDivision) -> Result<()> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .update_division(code, req)
+            .await
+    }
+
+    /// Removes the division `code`. A division that employees still belong
+    /// to can't be deleted; the API error tells why.
+    pub async fn delete(access_token: &str, code: &str) -> Result<()> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .delete_division(code)
+            .await
+    }
+
+    impl Client {
+        pub async fn divisions(&self) -> Result<Vec<Division>> {
+            self.get(endpoint::DIVISIONS)
+                .await
+                .map_err(|e| e.with_context(ErrorContext::new(Endpoint::Divisions)))
+        }
+
+        pub async fn create_division(&self, req: &CreateDivision) -> Result<()> {
+            let IgnoredAny = self
+                .post(endpoint::DIVISIONS, req)
+                .await
+                .map_err(|e| e.with_context(ErrorContext::new(Endpoint::Divisions)))?;
+            Ok(())
+        }
+
+        pub async fn update_division(&self, code: &str, req: &UpdateDivision) -> Result<()> {
+            let IgnoredAny = self
+                .put(&endpoint::division(code)?, req)
+                .await
+                .map_err(|e| e.with_context(ErrorContext::new(Endpoint::Division)))?;
+            Ok(())
+        }
+
+        pub async fn delete_division(&self, code: &str) -> Result<()> {
+            let IgnoredAny = self
+                .delete(&endpoint::division(code)?)
+                .await
+                .map_err(|e| e.with_context(ErrorContext::new(Endpoint::Division)))?;
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Division {
+        pub code: DivisionCode,
+        pub name: String,
+        /// When a working day ends in the division, e.g. 5:00.
+        #[serde(default, with = "crate::hh_mm::option")]
+        pub day_border_time: Option<NaiveTime>,
+        /// Position of the division in listings.
+        pub sort_order: Option<u32>,
+    }
+
+    #[test]
+    fn deserialize_list() {
+        let ex = r##"
+[
+  {
+    "code": "1000",
+    "name": "本社",
+    "dayBorderTime": "05:00",
+    "sortOrder": 1
+  },
+  {
+    "code": "2000",
+    "name": "大阪支社　営業部（第２課）",
+    "dayBorderTime": "00:00",
+    "sortOrder": 2
+  },
+  {
+    "code": "3000",
+    "name": "ｶﾅ・髙﨑工場"
+  }
+]
+        "##;
+
+        let divisions: Vec<Division> = serde_json::from_str(ex).unwrap();
+        let names: Vec<_> = divisions.iter().map(|d| &*d.name).collect();
+        assert_eq!(
+            names,
+            ["本社", "大阪支社　営業部（第２課）", "ｶﾅ・髙﨑工場"]
+        );
+        assert_eq!(
+            divisions[0].day_border_time,
+            NaiveTime::from_hms_opt(5, 0, 0)
+        );
+        assert_eq!(
+            serde_json::to_value(&divisions[0]).unwrap()["dayBorderTime"],
+            "05:00"
+        );
+        assert_eq!(divisions[1].sort_order, Some(2));
+        assert_eq!(divisions[2].day_border_time, None);
+    }
+
+    /// A new division, for [`create`].
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CreateDivision {
+        pub code: DivisionCode,
+        pub name: String,
+        /// The company's setting applies if unset.
+        #[serde(with = "crate::hh_mm::option", skip_serializing_if = "Option::is_none")]
+        pub day_border_time: Option<NaiveTime>,
+    }
+
+    #[test]
+    fn serialize_create_division() {
+        let req = CreateDivision {
+            code: "4000".into(),
+            name: "福岡営業所".into(),
+            day_border_time: NaiveTime::from_hms_opt(5, 0, 0),
+        };
+        let json = r##"
+        {
+            "code": "4000",
+            "name": "福岡営業所",
+            "dayBorderTime": "05:00"
+        }
+        "##;
+        let v1 = serde_json::from_str::<serde_json::Value>(json).unwrap();
+        assert_eq!(v1, serde_json::to_value(&req).unwrap());
+
+        let req = CreateDivision {
+            day_border_time: None,
+            ..req
+        };
+        assert_eq!(
+            serde_json::to_string(&req).unwrap(),
+            r#"{"code":"4000","name":"福岡営業所"}"#
+        );
+    }
+
+    /// Changes to a division, for [`update`]. The fields left to `None` are
+    /// not sent, and stay as they are.
+    #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct UpdateDivision {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[serde(with = "crate::hh_mm::option", skip_serializing_if = "Option::is_none")]
+        pub day_border_time: Option<NaiveTime>,
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn update_division() {
+        use crate::mock::{json, FakeTransport};
+        use http::Method;
+
+        let transport = FakeTransport::new(|_| json(200, ""));
+        let client = Client::with_transport(transport.clone(), "token");
+        let req = UpdateDivision {
+            name: Some("本社　総務部".into()),
+            ..Default::default()
+        };
+        client.update_division("1000/総務", &req).await.unwrap();
+
+        let sent = &transport.requests()[0];
+        assert_eq!(sent.method, Method::PUT);
+        assert_eq!(
+            sent.url.as_str(),
+            "https://api.kingtime.jp/v1.0/divisions/1000%2F%E7%B7%8F%E5%8B%99"
+        );
+        let body: serde_json::Value =
+            serde_json::from_slice(sent.body.as_deref().unwrap()).unwrap();
+        assert_eq!(body, serde_json::json!({"name": "本社　総務部"}));
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn delete_division() {
+        use crate::mock::{json, FakeTransport};
+        use http::Method;
+
+        let transport = FakeTransport::new(|req| match req.url.path() {
+            "/v1.0/divisions/1000" => json(
+                400,
+                r#"{"errors": [{"message": "所属している従業員がいるため削除できません", "code": 210}]}"#,
+            ),
+            "/v1.0/divisions/2000" => json(204, ""),
+            _ => json(200, ""),
+        });
+        let client = Client::with_transport(transport.clone(), "token");
+
+        client.delete_division("2000").await.unwrap();
+        client.delete_division("3000").await.unwrap();
+        assert_eq!(transport.requests()[0].method, Method::DELETE);
+
+        let e = client.delete_division("1000").await.unwrap_err();
+        let errors = e.api_errors().unwrap();
+        assert_eq!(errors[0].code, 210);
+        assert_eq!(
+            errors[0].message,
+            "所属している従業員がいるため削除できません"
+        );
+        assert!(
+            e.to_string().starts_with("divisions/{divisionCode}: "),
+            "{}",
+            e
+        );
+
+        // would delete from the API root
+        let e = client.delete_division("..").await.unwrap_err();
+        assert!(matches!(e, crate::Error::Builder(_)), "{:?}", e);
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn create_division() {
+        use crate::mock::{json, FakeTransport};
+        use crate::ErrorCode;
+        use http::Method;
+
+        let transport =
+            FakeTransport::new(|req| {
+                match serde_json::from_slice::<serde_json::Value>(req.body.as_deref().unwrap())
+                    .unwrap()["code"]
+                    .as_str()
+                {
+                    Some("1000") => json(
+                        400,
+                        r#"{"errors": [{"message": "既に登録されています", "code": 303}]}"#,
+                    ),
+                    _ => json(201, ""),
+                }
+            });
+        let client = Client::with_transport(transport.clone(), "token");
+        let req = CreateDivision {
+            code: "4000".into(),
+            name: "福岡営業所".into(),
+            day_border_time: None,
+        };
+
+        client.create_division(&req).await.unwrap();
+        let sent = &transport.requests()[0];
+        assert_eq!(sent.method, Method::POST);
+        assert_eq!(sent.url.as_str(), "https://api.kingtime.jp/v1.0/divisions");
+
+        let req = CreateDivision {
+            code: "1000".into(),
+            ..req
+        };
+        let e = client.create_division(&req).await.unwrap_err();
+        assert!(matches!(e.inner(), crate::Error::Api { .. }), "{:?}", e);
+        assert_eq!(
+            e.api_errors().unwrap()[0].code(),
+            ErrorCode::DuplicateRecord
+        );
+        assert!(e.is_duplicate_record());
+    }
+}
+
+pub mod daily_workings {
+    use super::{endpoint, Client, Endpoint, ErrorContext, Result};
+    pub use crate::CurrentDateEmployee;
+    use crate::{EmployeeKey, Minutes};
+    use chrono::{DateTime, FixedOffset, NaiveDate};
+    use serde::{Deserialize, Serialize};
+
+    pub async fn get(access_token: &str) -> Result<Response> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .daily_workings()
+            .await
+    }
+
+    /// Like [`get`], for the days and employees picked by `query`.
+    pub async fn get_with(access_token: &str, query: &Query) -> Result<Response> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .daily_workings_with(query)
+            .await
+    }
+
+    /// The daily workings of every employee on `date`, narrowed down by
+    /// `params`.
+    pub async fn get_by_date(
+        access_token: &str,
+        date: NaiveDate,
+        params: &Params,
+    ) -> Result<Vec<DailyWorking>> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .daily_workings_on(date, params)
+            .await
+    }
+
+    impl Client {
+        pub async fn daily_workings(&self) -> Result<Response> {
+            self.daily_workings_with(&Query::default()).await
+        }
+
+        /// Like [`Client::daily_workings`], for the days and employees
+        /// picked by `query`. A `start` after `end` fails with
+        /// [`Error::Builder`](crate::Error::Builder) without sending
+        /// anything.
+        pub async fn daily_workings_with(&self, query: &Query) -> Result<Response> {
+            let mut context = ErrorContext::new(Endpoint::DailyWorkings);
+            if let (Some(start), Some(end)) = (query.start, query.end) {
+                context = context.dates(start, end);
+            }
+            let res = match query.query_string() {
+                Ok(query) => self.get_with_query(endpoint::DAILY_WORKINGS, &query).await,
+                Err(e) => Err(e),
+            };
+            res.map_err(|e| e.with_context(context))
+        }
+
+        pub async fn daily_workings_on(
+            &self,
+            date: NaiveDate,
+            params: &Params,
+        ) -> Result<Vec<DailyWorking>> {
+            let day: DailyWorkings = self
+                .get_with_query(&endpoint::daily_workings_on(date), &params.query())
+                .await
+                .map_err(|e| {
+                    e.with_context(
+                        ErrorContext::new(Endpoint::DailyWorkingsOfDay).dates(date, date),
+                    )
+                })?;
+            Ok(day.daily_workings)
+        }
+    }
+
+    /// Filters of [`get_by_date`].
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct Params {
+        /// Only the employees of the division with this code.
+        pub division: Option<String>,
+        /// Fields left out of [`DailyWorking`] unless asked for.
+        pub additional_fields: Vec<AdditionalField>,
+    }
+
+    impl Params {
+        fn query(&self) -> QueryString<'_> {
+            QueryString {
+                division: self.division.as_deref(),
+                additional_fields: join(&self.additional_fields),
+                ..Default::default()
+            }
+        }
+    }
+
+    /// Filters of [`get_with`]. By default the API picks the days.
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct Query {
+        /// The first day, included.
+        pub start: Option<NaiveDate>,
+        /// The last day, included.
+        pub end: Option<NaiveDate>,
+        /// Only the records of the division with this code.
+        pub division: Option<String>,
+        /// Which records count as `division`'s.
+        pub ondivision: DivisionBasis,
+        /// Fields left out of [`DailyWorking`] unless asked for.
+        pub additional_fields: Vec<AdditionalField>,
+    }
+
+    /// How records are attributed to a division when filtering by one, the
+    /// `ondivision` query parameter.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum DivisionBasis {
+        /// The records of the employees belonging to the division.
+        #[default]
+        Home,
+        /// The records made at the division, whichever one the employee
+        /// belongs to.
+        Occurrence,
+    }
+
+    impl DivisionBasis {
+        pub(crate) fn query(self) -> Option<bool> {
+            Some(true).filter(|_| self == DivisionBasis::Occurrence)
+        }
+    }
+
+    impl Query {
+        fn query_string(&self) -> Result<QueryString<'_>> {
+            if let (Some(start), Some(end)) = (self.start, self.end) {
+                if start > end {
+                    return Err(crate::Error::Builder(format!(
+                        "start date {} is after end date {}",
+                        start, end
+                    )));
+                }
+            }
+            Ok(QueryString {
+                start: self.start,
+                end: self.end,
+                division: self.division.as_deref(),
+                ondivision: self.ondivision.query(),
+                additional_fields: join(&self.additional_fields),
+            })
+        }
+    }
+
+    #[derive(Default, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct QueryString<'a> {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start: Option<NaiveDate>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        end: Option<NaiveDate>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        division: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none", rename = "ondivision")]
+        ondivision: Option<bool>,
+        // comma separated
+        #[serde(skip_serializing_if = "Option::is_none")]
+        additional_fields: Option<String>,
+    }
+
+    fn join(fields: &[AdditionalField]) -> Option<String> {
+        let fields: Vec<_> = fields.iter().map(AdditionalField::as_str).collect();
+        Some(fields.join(",")).filter(|fields| !fields.is_empty())
+    }
+
+    #[test]
+    fn serialize_query() {
+        let query = Query {
+            start: NaiveDate::from_ymd_opt(2016, 5, 1),
+            end: NaiveDate::from_ymd_opt(2016, 5, 31),
+            division: Some("1000".into()),
+            ondivision: DivisionBasis::Occurrence,
+            additional_fields: vec![
+                AdditionalField::CurrentDateEmployee,
+                AdditionalField::CustomDailyWorkings,
+            ],
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string().unwrap()).unwrap(),
+            "start=2016-05-01&end=2016-05-31&division=1000&ondivision=true&additionalFields=currentDateEmployee%2CcustomDailyWorkings"
+        );
+        let query = Query {
+            division: Some("1000".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string().unwrap()).unwrap(),
+            "division=1000"
+        );
+        let query = Query::default();
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string().unwrap()).unwrap(),
+            ""
+        );
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn reject_backward_range() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|_| json(200, "[]"));
+        let client = Client::with_transport(transport.clone(), "token");
+        let query = Query {
+            start: NaiveDate::from_ymd_opt(2016, 5, 2),
+            end: NaiveDate::from_ymd_opt(2016, 5, 1),
+            ..Default::default()
+        };
+        let e = client.daily_workings_with(&query).await.unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "daily-workings (2016-05-02 to 2016-05-01): start date 2016-05-02 is after end date 2016-05-01"
+        );
+        assert!(transport.requests().is_empty());
+
+        let query = Query {
+            start: query.end,
+            ..query
+        };
+        client.daily_workings_with(&query).await.unwrap();
+        assert_eq!(
+            transport.requests()[0].url.as_str(),
+            "https://api.kingtime.jp/v1.0/daily-workings?start=2016-05-01&end=2016-05-01"
+        );
+    }
+
+    /// A field of [`DailyWorking`] left out unless asked for in [`Params`]
+    /// or [`Query`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum AdditionalField {
+        /// The employee as they were on the day.
+        CurrentDateEmployee,
+        /// The custom daily working items.
+        CustomDailyWorkings,
+    }
+
+    impl AdditionalField {
+        /// The name of the field in the API.
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                AdditionalField::CurrentDateEmployee => "currentDateEmployee",
+                AdditionalField::CustomDailyWorkings => "customDailyWorkings",
+            }
+        }
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn get_one_day() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|req| {
+            json(
+                200,
+                &format!(
+                    r#"{{"date": "2016-05-01", "dailyWorkings": [{{"date": "2016-05-01", "employeeKey": "{}"}}]}}"#,
+                    req.url.path()
+                ),
+            )
+        });
+        let client = Client::with_transport(transport.clone(), "token");
+        let date = NaiveDate::from_ymd_opt(2016, 5, 1).unwrap();
+
+        let workings = client
+            .daily_workings_on(date, &Params::default())
+            .await
+            .unwrap();
+        assert_eq!(workings[0].date, date);
+        assert_eq!(workings[0].employee_key, "/v1.0/daily-workings/2016-05-01");
+        let params = Params {
+            division: Some("1000".into()),
+            additional_fields: vec![AdditionalField::CurrentDateEmployee],
+        };
+        client.daily_workings_on(date, &params).await.unwrap();
+
+        let urls: Vec<_> = transport
+            .requests()
+            .iter()
+            .map(|req| req.url.to_string())
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://api.kingtime.jp/v1.0/daily-workings/2016-05-01",
+                "https://api.kingtime.jp/v1.0/daily-workings/2016-05-01?division=1000&additionalFields=currentDateEmployee",
+            ]
+        );
+    }
+
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    pub struct Response(pub Vec<DailyWorkings>);
+
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DailyWorkings {
+        pub date: NaiveDate,
+        pub daily_workings: Vec<DailyWorking>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DailyWorking {
+        pub date: NaiveDate,
+        pub employee_key: EmployeeKey,
+        /// The division the employee worked at.
+        pub work_place_division_code: Option<String>,
+        pub work_place_division_name: Option<String>,
+        /// Whether the day has been closed and can't change anymore.
+        #[serde(default)]
+        pub is_closing: bool,
+        /// Whether the employee worked at another division than their own.
+        #[serde(default)]
+        pub is_help: bool,
+        /// Whether the records of the day are inconsistent, e.g. a clock in
+        /// without a clock out.
+        #[serde(default)]
+        pub is_error: bool,
+        /// Such as 平日 or 法定休日.
+        pub workday_type_name: Option<String>,
+        // The durations below are 0 when left out.
+        /// Worked within the scheduled hours.
+        #[serde(default)]
+        pub assigned: Minutes,
+        /// Worked outside the scheduled hours.
+        #[serde(default)]
+        pub unassigned: Minutes,
+        #[serde(default)]
+        pub overtime: Minutes,
+        #[serde(default)]
+        pub late_night: Minutes,
+        #[serde(default)]
+        pub late_night_unassigned: Minutes,
+        #[serde(default)]
+        pub late_night_overtime: Minutes,
+        #[serde(default)]
+        pub break_time: Minutes,
+        #[serde(default)]
+        pub late: Minutes,
+        #[serde(default)]
+        pub early_leave: Minutes,
+        #[serde(default)]
+        pub total_work: Minutes,
+        /// 1 if the automatic break was turned off for the day.
+        pub auto_break_off: Option<u32>,
+        #[serde(default)]
+        pub discretionary_vacation: Minutes,
+        /// The holidays taken on the day, if any.
+        pub holidays_obtained: Option<HolidaysObtained>,
+        /// Only with [`AdditionalField::CurrentDateEmployee`].
+        pub current_date_employee: Option<CurrentDateEmployee>,
+        /// Only with [`AdditionalField::CustomDailyWorkings`].
+        #[serde(default)]
+        pub custom_daily_workings: Vec<CustomDailyWorking>,
+    }
+
+    /// The holidays of a [`DailyWorking`], by how much of the day they take.
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct HolidaysObtained {
+        pub fulltime_holiday: Option<FulltimeHoliday>,
+        #[serde(default)]
+        pub halfday_holidays: Vec<HalfdayHoliday>,
+        #[serde(default)]
+        pub hour_holidays: Vec<HourHoliday>,
+    }
+
+    /// A holiday taking the whole day.
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct FulltimeHoliday {
+        /// The code of the holiday type, such as 有休.
+        pub code: u32,
+        pub name: String,
+    }
+
+    /// A holiday taking half of the day.
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct HalfdayHoliday {
+        /// Which half, such as AM休 or PM休.
+        pub type_name: String,
+        pub code: u32,
+        pub name: String,
+    }
+
+    /// A holiday taken by the hour.
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct HourHoliday {
+        pub start: DateTime<FixedOffset>,
+        pub end: DateTime<FixedOffset>,
+        pub minutes: Minutes,
+        pub code: u32,
+        pub name: String,
+    }
+
+    /// The value of a custom daily working item.
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CustomDailyWorking {
+        pub code: String,
+        pub name: String,
+        #[serde(rename = "calculationUnitCode")]
+        pub calculation_unit: crate::CalculationUnit,
+        pub calculation_result: f64,
+    }
+
+    #[test]
+    fn deserialize_response() {
+        use crate::CalculationUnit;
+
+        let ex = r##"
+[
+  {
+    "date": "2016-05-01",
+    "dailyWorkings": [
+      {
+        "date": "2016-05-01",
+        "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+        "currentDateEmployee": {
+          "divisionCode": "1000",
+          "divisionName": "本社",
+          "gender": "male",
+          "typeCode": "1",
+          "typeName": "正社員",
+          "code": "1000",
+          "lastName": "勤怠",
+          "firstName": "太郎",
+          "lastNamePhonetics": "キンタイ",
+          "firstNamePhonetics": "タロウ",
+          "employeeGroups": [
+            {
+              "code": "0001",
+              "name": "人事部"
+            },
+            {
+              "code": "0002",
+              "name": "総務部"
+            }
+          ]
+        },
+        "workPlaceDivisionCode": "1000",
+        "workPlaceDivisionName": "本社",
+        "isClosing": true,
+        "isHelp": false,
+        "isError": false,
+        "workdayTypeName": "平日",
+        "assigned": 480,
+        "unassigned": 135,
+        "overtime": 135,
+        "lateNight": 0,
+        "lateNightUnassigned": 0,
+        "lateNightOvertime": 0,
+        "breakTime": 60,
+        "late": 0,
+        "earlyLeave": 0,
+        "totalWork": 615,
+        "holidaysObtained": {
+          "fulltimeHoliday": {
+            "code": 1,
+            "name": "有休"
+          },
+          "halfdayHolidays": [
+            {
+              "typeName": "PM休",
+              "code": 1,
+              "name": "有休"
+            }
+          ],
+          "hourHolidays": [
+            {
+              "start": "2016-05-01T10:00:00+09:00",
+              "end": "2016-05-01T11:00:00+09:00",
+              "minutes": 60,
+              "code": 1,
+              "name": "有休"
+            }
+          ]
+        },
+        "autoBreakOff": 1,
+        "discretionaryVacation": 0,
+        "customDailyWorkings": [
+          {
+            "code": "dCus1",
+            "name": "日別カスタム1",
+            "calculationUnitCode": 1,
+            "calculationResult": 1
+          },
+          {
+            "code": "dCus2",
+            "name": "日別カスタム2",
+            "calculationUnitCode": 2,
+            "calculationResult": 10
+          },
+          {
+            "code": "dCus3",
+            "name": "日別カスタム3",
+            "calculationUnitCode": 4,
+            "calculationResult": 100
+          }
+        ]
+      }
+    ]
+  }
+]
+        "##;
+
+        let Response(days) = serde_json::from_str(ex).unwrap();
+        let dw = &days[0].daily_workings[0];
+        assert_eq!(dw.work_place_division_code.as_deref(), Some("1000"));
+        assert_eq!(dw.work_place_division_name.as_deref(), Some("本社"));
+        assert!(dw.is_closing);
+        assert!(!dw.is_help);
+        assert!(!dw.is_error);
+        assert_eq!(dw.workday_type_name.as_deref(), Some("平日"));
+        assert_eq!(dw.assigned, Minutes(480));
+        assert_eq!(dw.unassigned, Minutes(135));
+        assert_eq!(dw.overtime, Minutes(135));
+        assert_eq!(dw.late_night, Minutes(0));
+        assert_eq!(dw.break_time, Minutes(60));
+        assert_eq!(dw.late, Minutes(0));
+        assert_eq!(dw.early_leave, Minutes(0));
+        assert_eq!(dw.total_work, Minutes(615));
+        assert_eq!(dw.assigned + dw.unassigned, dw.total_work);
+        assert_eq!(dw.auto_break_off, Some(1));
+        assert_eq!(dw.discretionary_vacation, Minutes(0));
+        let holidays = dw.holidays_obtained.as_ref().unwrap();
+        assert_eq!(holidays.fulltime_holiday.as_ref().unwrap().name, "有休");
+        assert_eq!(holidays.halfday_holidays.len(), 1);
+        assert_eq!(holidays.halfday_holidays[0].type_name, "PM休");
+        assert_eq!(holidays.halfday_holidays[0].code, 1);
+        let hour = &holidays.hour_holidays[0];
+        assert_eq!(hour.minutes, Minutes(60));
+        assert_eq!(hour.start.to_rfc3339(), "2016-05-01T10:00:00+09:00");
+        assert_eq!(hour.end.to_rfc3339(), "2016-05-01T11:00:00+09:00");
+        let employee = dw.current_date_employee.as_ref().unwrap();
+        assert_eq!(employee.code, "1000");
+        assert_eq!(employee.division_code.as_deref(), Some("1000"));
+        assert_eq!(employee.employee_groups[1].name, "総務部");
+        let customs: Vec<_> = dw
+            .custom_daily_workings
+            .iter()
+            .map(|custom| {
+                (
+                    &*custom.code,
+                    &*custom.name,
+                    custom.calculation_unit,
+                    custom.calculation_result,
+                )
+            })
+            .collect();
+        assert_eq!(
+            customs,
+            [
+                ("dCus1", "日別カスタム1", CalculationUnit::Days, 1.0),
+                ("dCus2", "日別カスタム2", CalculationUnit::Times, 10.0),
+                ("dCus3", "日別カスタム3", CalculationUnit::Minutes, 100.0),
+            ]
+        );
+
+        let resp = Response(days);
+        let json = serde_json::to_string(&resp).unwrap();
+        let back: Response = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            serde_json::to_value(&back).unwrap(),
+            serde_json::to_value(&resp).unwrap()
+        );
+        let back = &back.0[0].daily_workings[0];
+        assert_eq!(back.total_work, Minutes(615));
+        assert_eq!(
+            back.custom_daily_workings[2].calculation_unit,
+            CalculationUnit::Minutes
+        );
+        let hour = &back.holidays_obtained.as_ref().unwrap().hour_holidays[0];
+        assert_eq!(hour.start.to_rfc3339(), "2016-05-01T10:00:00+09:00");
+    }
+
+    #[test]
+    fn deserialize_day() {
+        let ex = r##"
+{
+  "date": "2016-05-01",
+  "dailyWorkings": [
+    {
+      "date": "2016-05-01",
+      "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+      "workPlaceDivisionCode": "1000",
+      "isClosing": true,
+      "totalWork": 615
+    },
+    {
+      "date": "2016-05-01",
+      "employeeKey": "c0ffee3bd9b27e0deb9f294a03e3a1b0e5c8ea8fa8372ce72f1a3ca1c3f7e9d2",
+      "workPlaceDivisionCode": "2000",
+      "isClosing": false,
+      "totalWork": 0
+    }
+  ]
+}
+        "##;
+
+        let day: DailyWorkings = serde_json::from_str(ex).unwrap();
+        assert_eq!(day.daily_workings.len(), 2);
+        assert_eq!(day.daily_workings[0].total_work, Minutes(615));
+        assert!(!day.daily_workings[1].is_closing);
+        assert_eq!(day.daily_workings[1].assigned, Minutes(0));
+        assert_eq!(day.daily_workings[1].workday_type_name, None);
+        assert!(day.daily_workings[0].current_date_employee.is_none());
+        assert!(day.daily_workings[0].holidays_obtained.is_none());
+        assert!(day.daily_workings[0].custom_daily_workings.is_empty());
+        assert_eq!(
+            day.daily_workings[1].employee_key,
+            "c0ffee3bd9b27e0deb9f294a03e3a1b0e5c8ea8fa8372ce72f1a3ca1c3f7e9d2"
+        );
+    }
+
+    pub mod timerecord {
+        use super::DivisionBasis;
+        use crate::{endpoint, Client, Endpoint, Error, ErrorContext, ResponseEnvelope, Result};
+        use crate::{CurrentDateEmployee, EmployeeKey};
+        use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+        use futures::stream::{self, StreamExt};
+        use http::Method;
+        use serde::de::{IgnoredAny, Visitor};
+        use serde::{Deserialize, Serialize};
+        use std::collections::BTreeMap;
+
+        /// Number of employees asked for in each request made by
+        /// [`Client::timerecords_many`].
+        pub const KEYS_PER_REQUEST: usize = 10;
+
+        pub async fn post(access_token: &str, key: &EmployeeKey, req: &Request) -> Result<()> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .post_timerecord(key, req)
+                .await
+        }
+
+        /// Deletes the time record of the employee `key` matching `req`.
+        pub async fn delete(
+            access_token: &str,
+            key: &EmployeeKey,
+            req: &DeleteRequest,
+        ) -> Result<()> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .delete_timerecord(key, req)
+                .await
+        }
+
+        impl Client {
+            pub async fn post_timerecord(&self, key: &EmployeeKey, req: &Request) -> Result<()> {
+                req.validate()?;
+                let IgnoredAny = self
+                    .post(&endpoint::timerecord(key.as_str())?, req)
+                    .await
+                    .map_err(|e| {
+                        e.with_context(
+                            ErrorContext::new(Endpoint::EmployeeTimeRecords)
+                                .employee(key.as_str())
+                                .dates(req.date, req.date),
+                        )
+                    })?;
+                Ok(())
+            }
+
+            /// Deletes the time record of the employee `key` matching `req`.
+            /// A record that doesn't exist fails with an error for which
+            /// [`Error::is_not_found`] holds.
+            pub async fn delete_timerecord(
+                &self,
+                key: &EmployeeKey,
+                req: &DeleteRequest,
+            ) -> Result<()> {
+                let IgnoredAny = self
+                    .request(
+                        Method::DELETE,
+                        &endpoint::timerecord(key.as_str())?,
+                        None::<&()>,
+                        Some(req),
+                    )
+                    .await
+                    .map_err(|e| {
+                        e.with_context(
+                            ErrorContext::new(Endpoint::EmployeeTimeRecords)
+                                .employee(key.as_str())
+                                .dates(req.date, req.date),
+                        )
+                    })?;
+                Ok(())
+            }
+
+            /// The time records of `keys` from `start` to `end`. An empty
+            /// `keys` has none, and is answered without a request; ask
+            /// [`Client::all_timerecords`] for those of every employee.
+            pub async fn timerecords(
+                &self,
+                keys: &[EmployeeKey],
+                start: NaiveDate,
+                end: NaiveDate,
+            ) -> Result<Response> {
+                self.timerecords_with(keys, start, end, &Filter::default())
+                    .await
+            }
+
+            /// Like [`Client::timerecords`], only for the records picked by
+            /// `filter`.
+            pub async fn timerecords_with(
+                &self,
+                keys: &[EmployeeKey],
+                start: NaiveDate,
+                end: NaiveDate,
+                filter: &Filter,
+            ) -> Result<Response> {
+                Ok(self
+                    .timerecords_query(Some(keys), start, end, filter)
+                    .await?
+                    .into_data())
+            }
+
+            /// Like [`Client::timerecords_with`], for every employee of the
+            /// company.
+            pub async fn all_timerecords(
+                &self,
+                start: NaiveDate,
+                end: NaiveDate,
+                filter: &Filter,
+            ) -> Result<Response> {
+                Ok(self
+                    .timerecords_query(None, start, end, filter)
+                    .await?
+                    .into_data())
+            }
+
+            /// The time records of `keys` on `date`, none for an empty `keys`,
+            /// like [`Client::timerecords`].
+            pub async fn timerecords_on(
+                &self,
+                keys: &[EmployeeKey],
+                date: NaiveDate,
+            ) -> Result<Vec<DailyWorking>> {
+                if keys.is_empty() {
+                    return Ok(vec![]);
+                }
+                let query = [("employeeKeys", keys.join(","))];
+                let day: DailyWorkings = self
+                    .get_with_query(&endpoint::timerecords_on(date), &query)
+                    .await
+                    .map_err(|e| {
+                        e.with_context(
+                            ErrorContext::new(Endpoint::TimeRecordsOfDay)
+                                .employees(keys)
+                                .dates(date, date),
+                        )
+                    })?;
+                Ok(day.daily_workings)
+            }
+
+            /// Like [`Client::timerecords`], also returning the status,
+            /// headers and timing of the response.
+            pub async fn timerecords_with_meta(
+                &self,
+                keys: &[EmployeeKey],
+                start: NaiveDate,
+                end: NaiveDate,
+            ) -> Result<ResponseEnvelope<Response>> {
+                self.timerecords_query(Some(keys), start, end, &Filter::default())
+                    .await
+            }
+
+            // `None` asks for every employee, an empty list for none of them.
+            async fn timerecords_query(
+                &self,
+                keys: Option<&[EmployeeKey]>,
+                start: NaiveDate,
+                end: NaiveDate,
+                filter: &Filter,
+            ) -> Result<ResponseEnvelope<Response>> {
+                if matches!(keys, Some([])) {
+                    return Ok(ResponseEnvelope {
+                        data: Response(vec![]),
+                        status: http::StatusCode::OK,
+                        headers: Default::default(),
+                        elapsed: Default::default(),
+                    });
+                }
+                let fields: Vec<_> = filter
+                    .additional_fields
+                    .iter()
+                    .map(AdditionalField::as_str)
+                    .collect();
+                let query = Query {
+                    employee_keys: keys.map(|keys| keys.join(",")),
+                    start,
+                    end,
+                    division: filter.division.as_deref(),
+                    ondivision: filter.ondivision.query(),
+                    additional_fields: Some(fields.join(",")).filter(|fields| !fields.is_empty()),
+                };
+                self.request_with_meta(
+                    Method::GET,
+                    endpoint::TIMERECORDS,
+                    Some(&query),
+                    None::<&()>,
+                )
+                .await
+                .map_err(|e| {
+                    e.with_context(
+                        ErrorContext::new(Endpoint::TimeRecords)
+                            .employees(keys.unwrap_or_default())
+                            .dates(start, end),
+                    )
+                })
+            }
+
+            /// Fetches the time records of `keys` in chunks of
+            /// [`KEYS_PER_REQUEST`] employees, with at most `concurrency`
+            /// requests in flight.
+            ///
+            /// A failed chunk doesn't stop the others; it is reported in
+            /// [`ManyResponse::failures`] and its employees are missing from
+            /// the merged response.
+            pub async fn timerecords_many(
+                &self,
+                keys: &[EmployeeKey],
+                start: NaiveDate,
+                end: NaiveDate,
+                concurrency: usize,
+            ) -> ManyResponse {
+                let results: Vec<_> = stream::iter(keys.chunks(KEYS_PER_REQUEST))
+                    .map(|chunk| async move { (chunk, self.timerecords(chunk, start, end).await) })
+                    .buffered(concurrency.max(1))
+                    .collect()
+                    .await;
+
+                let mut dates = BTreeMap::<_, Vec<_>>::new();
+                let mut failures = vec![];
+                for (chunk, res) in results {
+                    match res {
+                        Ok(Response(days)) => {
+                            for day in days {
+                                dates
+                                    .entry(day.date)
+                                    .or_default()
+                                    .extend(day.daily_workings);
+                            }
+                        }
+                        Err(error) => failures.push(ChunkError {
+                            keys: chunk.to_vec(),
+                            error,
+                        }),
+                    }
+                }
+                let days = dates
+                    .into_iter()
+                    .map(|(date, daily_workings)| DailyWorkings {
+                        date,
+                        daily_workings,
+                    })
+                    .collect();
+                ManyResponse {
+                    response: Response(days),
+                    failures,
+                }
+            }
+        }
+
+        /// Filters of [`get_with`].
+        #[derive(Debug, Clone, PartialEq, Eq, Default)]
+        pub struct Filter {
+            /// Only the records of the division with this code.
+            pub division: Option<String>,
+            /// Which records count as `division`'s.
+            pub ondivision: DivisionBasis,
+            pub additional_fields: Vec<AdditionalField>,
+        }
+
+        /// A field of [`DailyWorking`] left out unless asked for in
+        /// [`Filter`].
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum AdditionalField {
+            /// The employee as they were on the day.
+            CurrentDateEmployee,
+        }
+
+        impl AdditionalField {
+            /// The name of the field in the API.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    AdditionalField::CurrentDateEmployee => "currentDateEmployee",
+                }
+            }
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Query<'a> {
+            // comma separated
+            #[serde(skip_serializing_if = "Option::is_none")]
+            employee_keys: Option<String>,
+            start: NaiveDate,
+            end: NaiveDate,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            division: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none", rename = "ondivision")]
+            ondivision: Option<bool>,
+            // comma separated
+            #[serde(skip_serializing_if = "Option::is_none")]
+            additional_fields: Option<String>,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct Request {
+            pub date: NaiveDate,
+            #[serde(with = "crate::ts_seconds_jst")]
+            pub time: DateTime<Utc>,
+            pub code: Code,
+            /// The division the record is made at, if not the employee's
+            /// own.
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub division_code: Option<String>,
+            /// Where the record is made, in degrees. Out of range values
+            /// are refused before anything is sent.
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub latitude: Option<f64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub longitude: Option<f64>,
+            /// The credential the record is made with, such as a badge
+            /// reader, read back as [`TimeRecord::credential_code`].
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub credential_code: Option<u32>,
+        }
+
+        impl Request {
+            /// A record of `code` at `time`, counted on the working day
+            /// `date`.
+            pub fn new(date: NaiveDate, time: DateTime<Utc>, code: Code) -> Self {
+                Request {
+                    date,
+                    time,
+                    code,
+                    division_code: None,
+                    latitude: None,
+                    longitude: None,
+                    credential_code: None,
+                }
+            }
+
+            fn validate(&self) -> Result<()> {
+                let check = |name: &str, value: Option<f64>, bound: f64| match value {
+                    // NaN is in no range
+                    Some(value) if !(-bound..=bound).contains(&value) => Err(Error::Builder(
+                        format!("{} {} is not in -{}..={}", name, value, bound, bound),
+                    )),
+                    _ => Ok(()),
+                };
+                check("latitude", self.latitude, 90.0)?;
+                check("longitude", self.longitude, 180.0)
+            }
+        }
+
+        /// Identifies the time record to delete with [`delete`].
+        #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct DeleteRequest {
+            pub date: NaiveDate,
+            #[serde(with = "crate::ts_seconds_jst")]
+            pub time: DateTime<Utc>,
+            pub code: Code,
+        }
+
+        #[test]
+        fn serialize_request() {
+            let req = Request::new(
+                "2016-05-01".parse().unwrap(),
+                "2016-05-01T09:00:00+09:00".parse().unwrap(),
+                Code::BreakEnd,
+            );
+
+            let json = r##"
+            {
+                "date": "2016-05-01",
+                "time": "2016-05-01T09:00:00+09:00",
+                "code": "4"
+            }
+            "##;
+
+            let v1 = serde_json::from_str::<serde_json::Value>(json).unwrap();
+            let v2 =
+                serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&req).unwrap())
+                    .unwrap();
+
+            assert_eq!(v1, v2);
+        }
+
+        #[test]
+        fn serialize_request_at_division() {
+            let req = Request {
+                division_code: Some("2000".into()),
+                ..Request::new(
+                    "2016-05-01".parse().unwrap(),
+                    "2016-05-01T09:00:00+09:00".parse().unwrap(),
+                    Code::In,
+                )
+            };
+
+            let json = r##"
+            {
+                "date": "2016-05-01",
+                "time": "2016-05-01T09:00:00+09:00",
+                "code": "1",
+                "divisionCode": "2000"
+            }
+            "##;
+
+            let v1 = serde_json::from_str::<serde_json::Value>(json).unwrap();
+            let v2 =
+                serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&req).unwrap())
+                    .unwrap();
+
+            assert_eq!(v1, v2);
+        }
+
+        #[test]
+        fn serialize_request_with_credential() {
+            let req = Request {
+                credential_code: Some(300),
+                ..Request::new(
+                    "2016-05-01".parse().unwrap(),
+                    "2016-05-01T09:00:00+09:00".parse().unwrap(),
+                    Code::In,
+                )
+            };
+
+            let json = r##"
+            {
+                "date": "2016-05-01",
+                "time": "2016-05-01T09:00:00+09:00",
+                "code": "1",
+                "credentialCode": 300
+            }
+            "##;
+
+            let v1 = serde_json::from_str::<serde_json::Value>(json).unwrap();
+            let v2 =
+                serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&req).unwrap())
+                    .unwrap();
+
+            assert_eq!(v1, v2);
+        }
+
+        #[test]
+        fn serialize_request_at_location() {
+            let req = Request {
+                latitude: Some(35.681236),
+                longitude: Some(139.767125),
+                ..Request::new(
+                    "2016-05-01".parse().unwrap(),
+                    "2016-05-01T09:00:00+09:00".parse().unwrap(),
+                    Code::In,
+                )
+            };
+
+            let json = r##"
+            {
+                "date": "2016-05-01",
+                "time": "2016-05-01T09:00:00+09:00",
+                "code": "1",
+                "latitude": 35.681236,
+                "longitude": 139.767125
+            }
+            "##;
+
+            let v1 = serde_json::from_str::<serde_json::Value>(json).unwrap();
+            let v2 =
+                serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&req).unwrap())
+                    .unwrap();
+            assert_eq!(v1, v2);
+
+            let req = Request {
+                latitude: None,
+                longitude: None,
+                ..req
+            };
+            let v = serde_json::to_value(&req).unwrap();
+            assert!(v.get("latitude").is_none());
+            assert!(v.get("longitude").is_none());
+        }
+
+        #[test]
+        fn serialize_delete_request() {
+            let req = DeleteRequest {
+                date: "2016-05-01".parse().unwrap(),
+                time: "2016-05-01T00:30:00Z".parse().unwrap(),
+                code: Code::In,
+            };
+
+            let json = r##"
+            {
+                "date": "2016-05-01",
+                "time": "2016-05-01T09:30:00+09:00",
+                "code": "1"
+            }
+            "##;
+
+            let v1 = serde_json::from_str::<serde_json::Value>(json).unwrap();
+            let v2 =
+                serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&req).unwrap())
+                    .unwrap();
+
+            assert_eq!(v1, v2);
+        }
+
+        /// The time records of `keys` from `start` to `end`, none for an
+        /// empty `keys`; [`get_all`] is the one for every employee.
+        pub async fn get(
+            access_token: &str,
+            keys: &[EmployeeKey],
+            start: NaiveDate,
+            end: NaiveDate,
+        ) -> Result<Response> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .timerecords(keys, start, end)
+                .await
+        }
+
+        /// Like [`get`], only for the records picked by `filter`.
+        pub async fn get_with(
+            access_token: &str,
+            keys: &[EmployeeKey],
+            start: NaiveDate,
+            end: NaiveDate,
+            filter: &Filter,
+        ) -> Result<Response> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .timerecords_with(keys, start, end, filter)
+                .await
+        }
+
+        /// Like [`get_with`], for every employee of the company rather than
+        /// only some.
+        pub async fn get_all(
+            access_token: &str,
+            start: NaiveDate,
+            end: NaiveDate,
+            filter: &Filter,
+        ) -> Result<Response> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .all_timerecords(start, end, filter)
+                .await
+        }
+
+        /// Like [`get`], for a single day.
+        pub async fn get_by_date(
+            access_token: &str,
+            keys: &[EmployeeKey],
+            date: NaiveDate,
+        ) -> Result<Vec<DailyWorking>> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .timerecords_on(keys, date)
+                .await
+        }
+
+        pub async fn get_with_meta(
+            access_token: &str,
+            keys: &[EmployeeKey],
+            start: NaiveDate,
+            end: NaiveDate,
+        ) -> Result<ResponseEnvelope<Response>> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .timerecords_with_meta(keys, start, end)
+                .await
+        }
+
+        pub async fn get_many(
+            access_token: &str,
+            keys: &[EmployeeKey],
+            start: NaiveDate,
+            end: NaiveDate,
+            concurrency: usize,
+        ) -> Result<ManyResponse> {
+            let client = Client::builder().access_token(access_token).build()?;
+            Ok(client.timerecords_many(keys, start, end, concurrency).await)
+        }
+
+        #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+        pub struct Response(pub Vec<DailyWorkings>);
+
+        /// The outcome of [`Client::timerecords_many`].
+        #[derive(Debug)]
+        pub struct ManyResponse {
+            /// The records of every chunk that succeeded, grouped by date.
+            pub response: Response,
+            pub failures: Vec<ChunkError>,
+        }
+
+        #[derive(Debug)]
+        pub struct ChunkError {
+            pub keys: Vec<EmployeeKey>,
+            pub error: Error,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct DailyWorkings {
+            pub date: NaiveDate,
+            pub daily_workings: Vec<DailyWorking>,
+        }
+
+        #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct DailyWorking {
+            pub date: NaiveDate,
+            pub employee_key: EmployeeKey,
+            pub time_record: Vec<TimeRecord>,
+            /// Only with [`AdditionalField::CurrentDateEmployee`].
+            pub current_date_employee: Option<CurrentDateEmployee>,
+        }
+
+        /// A punch. Only `time` and `code` are always there.
+        #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct TimeRecord {
+            /// As sent, in JST.
+            pub time: DateTime<FixedOffset>,
+            pub code: Code,
+            /// The name of `code`, such as 出勤.
+            pub name: Option<String>,
+            pub division_code: Option<String>,
+            pub division_name: Option<String>,
+            pub credential_code: Option<u32>,
+            pub credential_name: Option<String>,
+            pub latitude: Option<f64>,
+            pub longitude: Option<f64>,
+        }
+
+        impl TimeRecord {
+            /// A record of `code` at `time` without a credential, for
+            /// fixtures.
+            pub fn new(time: DateTime<FixedOffset>, code: Code) -> Self {
+                TimeRecord {
+                    time,
+                    code,
+                    name: None,
+                    division_code: None,
+                    division_name: None,
+                    credential_code: None,
+                    credential_name: None,
+                    latitude: None,
+                    longitude: None,
+                }
+            }
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum Code {
+            In,
+            Out,
+            BreakStart,
+            BreakEnd,
+            /// A code this crate doesn't know of, such as the ones some
+            /// companies have for going straight to a site, as sent.
+            Other(String),
+        }
+
+        struct CodeVisitor;
+
+        impl<'de> Visitor<'de> for CodeVisitor {
+            type Value = Code;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("code must be an str")
+            }
+
+            fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
+            where
                 E: serde::de::Error,
             {
-                let c = match v {
-                    "1" => Code::In,
-                    "2" => Code::Out,
-                    "3" => Code::BreakStart,
-                    "4" => Code::BreakEnd,
-                    _ => return Err(E::custom(format!("unknown code: {}", v))),
+                let c = match v {
+                    "1" => Code::In,
+                    "2" => Code::Out,
+                    "3" => Code::BreakStart,
+                    "4" => Code::BreakEnd,
+                    _ => Code::Other(v.to_string()),
+                };
+                Ok(c)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Code {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                deserializer.deserialize_str(CodeVisitor)
+            }
+        }
+
+        impl Serialize for Code {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                match self {
+                    Code::In => serializer.serialize_str("1"),
+                    Code::Out => serializer.serialize_str("2"),
+                    Code::BreakStart => serializer.serialize_str("3"),
+                    Code::BreakEnd => serializer.serialize_str("4"),
+                    Code::Other(code) => serializer.serialize_str(code),
+                }
+            }
+        }
+
+        #[test]
+        fn deserialize_response() {
+            use chrono::Timelike;
+
+            let ex = r##"
+            [
+                {
+                  "date": "2016-05-01",
+                  "dailyWorkings": [
+                    {
+                      "date": "2016-05-01",
+                      "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+                      "currentDateEmployee": {
+                        "divisionCode": "1000",
+                        "divisionName": "本社",
+                        "gender": "male",
+                        "typeCode": "1",
+                        "typeName": "正社員",
+                        "code": "1000",
+                        "lastName": "勤怠",
+                        "firstName": "太郎",
+                        "lastNamePhonetics": "キンタイ",
+                        "firstNamePhonetics": "タロウ",
+                        "employeeGroups": [
+                          {
+                            "code": "0001",
+                            "name": "人事部"
+                          },
+                          {
+                            "code": "0002",
+                            "name": "総務部"
+                          }
+                        ]
+                      },
+                      "timeRecord": [
+                        {
+                          "time": "2016-05-01T09:00:00+09:00",
+                          "code": "1",
+                          "name": "出勤",
+                          "divisionCode": "1000",
+                          "divisionName": "本社",
+                          "latitude": 35.6672237,
+                          "longitude": 139.7422207
+                        },
+                        {
+                          "time": "2015-05-01T18:00:00+09:00",
+                          "code": "2",
+                          "name": "退勤",
+                          "divisionCode": "1000",
+                          "divisionName": "本社",
+                          "credentialCode": 300,
+                          "credentialName": "KOTSL",
+                          "latitude": 35.6672237,
+                          "longitude": 139.7422207
+                        },
+                        {
+                          "time": "2016-05-01T10:00:00+09:00",
+                          "code": "3",
+                          "name": "休憩開始",
+                          "divisionCode": "1000",
+                          "divisionName": "本社"
+                        },
+                        {
+                          "time": "2016-05-01T11:00:00+09:00",
+                          "code": "4",
+                          "name": "休憩終了",
+                          "divisionCode": "1000",
+                          "divisionName": "本社"
+                        }
+                      ]
+                    }
+                  ]
+                }
+              ]
+            "##;
+
+            let res: Response = serde_json::from_str(ex).unwrap();
+            let working = &res.0[0].daily_workings[0];
+            let employee = working.current_date_employee.as_ref().unwrap();
+            assert_eq!(employee.code, "1000");
+            assert_eq!(employee.division_name.as_deref(), Some("本社"));
+            assert_eq!(employee.type_name.as_deref(), Some("正社員"));
+            assert_eq!(employee.first_name_phonetics.as_deref(), Some("タロウ"));
+            assert_eq!(employee.employee_groups.len(), 2);
+            assert_eq!(employee.employee_groups[0].name, "人事部");
+
+            let records = &working.time_record;
+            assert_eq!(records.len(), 4);
+            assert_eq!(records[0].time.hour(), 9);
+            assert_eq!(records[0].time.offset().local_minus_utc(), 9 * 3600);
+            assert_eq!(records[0].time.date_naive(), working.date);
+            assert_eq!(records[0].name.as_deref(), Some("出勤"));
+            assert_eq!(records[0].division_code.as_deref(), Some("1000"));
+            assert_eq!(records[0].division_name.as_deref(), Some("本社"));
+            assert_eq!(records[0].latitude, Some(35.6672237));
+            assert_eq!(records[0].longitude, Some(139.7422207));
+            assert_eq!(records[2].code, Code::BreakStart);
+            assert_eq!(records[2].latitude, None);
+            assert_eq!(records[0].credential_code, None);
+            assert_eq!(records[1].credential_code, Some(300));
+            assert_eq!(records[1].credential_name.as_deref(), Some("KOTSL"));
+            assert!((records[1].latitude.unwrap() - 35.667).abs() < 0.001);
+
+            let json = serde_json::to_string(&res).unwrap();
+            let back: Response = serde_json::from_str(&json).unwrap();
+            assert_eq!(
+                serde_json::to_value(&back).unwrap(),
+                serde_json::to_value(&res).unwrap()
+            );
+            let record = &back.0[0].daily_workings[0].time_record[0];
+            assert_eq!(record.time, records[0].time);
+            assert_eq!(record.code, Code::In);
+            assert_eq!(
+                serde_json::to_value(record).unwrap()["time"],
+                "2016-05-01T09:00:00+09:00"
+            );
+        }
+
+        #[test]
+        fn keep_unknown_codes() {
+            let ex = r##"
+            [
+                {
+                  "date": "2016-05-01",
+                  "dailyWorkings": [
+                    {
+                      "date": "2016-05-01",
+                      "employeeKey": "8b6ee646",
+                      "timeRecord": [
+                        {"time": "2016-05-01T08:00:00+09:00", "code": "5", "name": "直行"},
+                        {"time": "2016-05-01T09:00:00+09:00", "code": "1", "name": "出勤"}
+                      ]
+                    }
+                  ]
+                }
+            ]
+            "##;
+
+            let res: Response = serde_json::from_str(ex).unwrap();
+            let codes: Vec<_> = res.0[0].daily_workings[0]
+                .time_record
+                .iter()
+                .map(|record| record.code.clone())
+                .collect();
+            assert_eq!(codes, [Code::Other("5".into()), Code::In]);
+            assert_eq!(
+                serde_json::to_string(&Code::Other("5".into())).unwrap(),
+                r#""5""#
+            );
+            assert_eq!(serde_json::to_string(&Code::BreakEnd).unwrap(), r#""4""#);
+        }
+
+        #[test]
+        fn deserialize_bare_record() {
+            use chrono::Timelike;
+
+            let record: TimeRecord =
+                serde_json::from_str(r#"{"time": "2016-05-01T09:00:00+09:00", "code": "1"}"#)
+                    .unwrap();
+            assert_eq!(
+                record,
+                TimeRecord::new("2016-05-01T09:00:00+09:00".parse().unwrap(), Code::In)
+            );
+            assert_eq!(record.time.hour(), 9);
+            assert_eq!(record.time.date_naive(), "2016-05-01".parse().unwrap());
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn get_through_transport() {
+            use crate::mock::{json, FakeTransport};
+
+            let transport = FakeTransport::new(|_| {
+                json(
+                    200,
+                    r#"[{"date": "2016-05-01", "dailyWorkings": [{"date": "2016-05-01", "employeeKey": "a", "timeRecord": [{"time": "2016-05-01T09:00:00+09:00", "code": "1"}]}]}]"#,
+                )
+            });
+            let client = Client::with_transport(transport.clone(), "token");
+            let start = "2016-05-01".parse().unwrap();
+            let resp = client
+                .timerecords(&["a".into(), "b".into()], start, start)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                resp.0[0].daily_workings,
+                [DailyWorking {
+                    date: start,
+                    employee_key: "a".into(),
+                    time_record: vec![TimeRecord::new(
+                        "2016-05-01T09:00:00+09:00".parse().unwrap(),
+                        Code::In
+                    )],
+                    current_date_employee: None,
+                }]
+            );
+
+            let req = &transport.requests()[0];
+            assert_eq!(req.method, http::Method::GET);
+            assert_eq!(
+                req.url.as_str(),
+                "https://api.kingtime.jp/v1.0/daily-workings/timerecord?employeeKeys=a%2Cb&start=2016-05-01&end=2016-05-01"
+            );
+            assert_eq!(req.headers["authorization"], "Bearer token");
+        }
+
+        #[test]
+        fn deserialize_day() {
+            let ex = r##"
+{
+  "date": "2016-05-01",
+  "dailyWorkings": [
+    {
+      "date": "2016-05-01",
+      "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+      "timeRecord": [
+        {
+          "time": "2016-05-01T09:00:00+09:00",
+          "code": "1",
+          "name": "出勤"
+        },
+        {
+          "time": "2016-05-01T18:00:00+09:00",
+          "code": "2",
+          "name": "退勤"
+        }
+      ]
+    }
+  ]
+}
+            "##;
+
+            let day: DailyWorkings = serde_json::from_str(ex).unwrap();
+            let codes: Vec<_> = day.daily_workings[0]
+                .time_record
+                .iter()
+                .map(|record| record.code.clone())
+                .collect();
+            assert_eq!(codes, [Code::In, Code::Out]);
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn get_one_day() {
+            use crate::mock::{json, FakeTransport};
+
+            let transport = FakeTransport::new(|_| {
+                json(
+                    200,
+                    r#"{"date": "2016-05-01", "dailyWorkings": [{"date": "2016-05-01", "employeeKey": "a", "timeRecord": []}]}"#,
+                )
+            });
+            let client = Client::with_transport(transport.clone(), "token");
+            let date = NaiveDate::from_ymd_opt(2016, 5, 1).unwrap();
+            let workings = client
+                .timerecords_on(&["a".into(), "b".into()], date)
+                .await
+                .unwrap();
+            assert_eq!(workings[0].employee_key, "a");
+            assert!(client.timerecords_on(&[], date).await.unwrap().is_empty());
+            assert_eq!(transport.requests().len(), 1);
+            assert_eq!(
+                transport.requests()[0].url.as_str(),
+                "https://api.kingtime.jp/v1.0/daily-workings/timerecord/2016-05-01?employeeKeys=a%2Cb"
+            );
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn filter_by_division() {
+            use crate::mock::{json, FakeTransport};
+
+            let transport = FakeTransport::new(|_| json(200, "[]"));
+            let client = Client::with_transport(transport.clone(), "token");
+            let date = "2016-05-01".parse().unwrap();
+            let mut filter = Filter {
+                division: Some("1000".into()),
+                ..Default::default()
+            };
+            client
+                .timerecords_with(&["a".into()], date, date, &filter)
+                .await
+                .unwrap();
+            filter.ondivision = DivisionBasis::Occurrence;
+            client
+                .timerecords_with(&["a".into()], date, date, &filter)
+                .await
+                .unwrap();
+            let filter = Filter {
+                additional_fields: vec![AdditionalField::CurrentDateEmployee],
+                ..Default::default()
+            };
+            client
+                .timerecords_with(&["a".into()], date, date, &filter)
+                .await
+                .unwrap();
+            client
+                .all_timerecords(date, date, &Filter::default())
+                .await
+                .unwrap();
+            // not everyone's
+            let none = client
+                .timerecords_with(&[], date, date, &Filter::default())
+                .await
+                .unwrap();
+            assert!(none.0.is_empty());
+            let none = client.timerecords_with_meta(&[], date, date).await.unwrap();
+            assert!(none.data().0.is_empty());
+
+            let urls: Vec<_> = transport
+                .requests()
+                .iter()
+                .map(|req| req.url.to_string())
+                .collect();
+            assert_eq!(
+                urls,
+                [
+                    "https://api.kingtime.jp/v1.0/daily-workings/timerecord?employeeKeys=a&start=2016-05-01&end=2016-05-01&division=1000",
+                    "https://api.kingtime.jp/v1.0/daily-workings/timerecord?employeeKeys=a&start=2016-05-01&end=2016-05-01&division=1000&ondivision=true",
+                    "https://api.kingtime.jp/v1.0/daily-workings/timerecord?employeeKeys=a&start=2016-05-01&end=2016-05-01&additionalFields=currentDateEmployee",
+                    "https://api.kingtime.jp/v1.0/daily-workings/timerecord?start=2016-05-01&end=2016-05-01",
+                ]
+            );
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn post_without_content() {
+            use crate::mock::{json, FakeTransport};
+
+            let req = Request::new(
+                "2016-05-01".parse().unwrap(),
+                "2016-05-01T09:00:00+09:00".parse().unwrap(),
+                Code::In,
+            );
+            for &(status, body) in &[(200, ""), (204, ""), (201, " \r\n"), (200, "{}")] {
+                let transport = FakeTransport::new(move |_| json(status, body));
+                let client = Client::with_transport(transport, "token");
+                let res = client.post_timerecord(&"a".into(), &req).await;
+                assert!(res.is_ok(), "{} {:?}: {:?}", status, body, res);
+            }
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn refuse_out_of_range_location() {
+            use crate::mock::{json, FakeTransport};
+
+            let transport = FakeTransport::new(|_| json(204, ""));
+            let client = Client::with_transport(transport.clone(), "token");
+            let at = |latitude, longitude| Request {
+                latitude,
+                longitude,
+                ..Request::new(
+                    "2016-05-01".parse().unwrap(),
+                    "2016-05-01T09:00:00+09:00".parse().unwrap(),
+                    Code::In,
+                )
+            };
+            for &(latitude, longitude) in &[
+                (Some(90.5), None),
+                (None, Some(-180.5)),
+                (Some(f64::NAN), Some(0.0)),
+            ] {
+                let e = client
+                    .post_timerecord(&"a".into(), &at(latitude, longitude))
+                    .await
+                    .unwrap_err();
+                assert!(matches!(e, Error::Builder(_)), "{:?}", e);
+            }
+            assert!(transport.requests().is_empty());
+
+            client
+                .post_timerecord(&"a".into(), &at(Some(-90.0), Some(180.0)))
+                .await
+                .unwrap();
+            assert_eq!(transport.requests().len(), 1);
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn delete_record() {
+            use crate::mock::{json, FakeTransport};
+
+            let req = DeleteRequest {
+                date: "2016-05-01".parse().unwrap(),
+                time: "2016-05-01T09:00:00+09:00".parse().unwrap(),
+                code: Code::In,
+            };
+            let transport = FakeTransport::new(|req| match req.body.as_deref() {
+                Some(body) if body.starts_with(br#"{"date":"2016-05-01""#) => json(204, ""),
+                _ => json(404, r#"{"errors": [{"message": "", "code": 302}]}"#),
+            });
+            let client = Client::with_transport(transport.clone(), "token");
+            client.delete_timerecord(&"a".into(), &req).await.unwrap();
+            let sent = &transport.requests()[0];
+            assert_eq!(sent.method, http::Method::DELETE);
+            assert_eq!(
+                sent.url.as_str(),
+                "https://api.kingtime.jp/v1.0/daily-workings/timerecord/a"
+            );
+
+            let req = DeleteRequest {
+                date: "2016-05-02".parse().unwrap(),
+                ..req
+            };
+            let e = client
+                .delete_timerecord(&"a".into(), &req)
+                .await
+                .unwrap_err();
+            assert!(e.is_not_found());
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn describe_failed_calls() {
+            use crate::mock::{json, FakeTransport};
+
+            let transport = FakeTransport::new(|_| {
+                json(
+                    404,
+                    r#"{"errors": [{"message": "対象が存在しません", "code": 302}]}"#,
+                )
+            });
+            let client = Client::with_transport(transport, "token");
+            let date = "2016-05-01".parse().unwrap();
+
+            let req = Request::new(date, "2016-05-01T09:00:00+09:00".parse().unwrap(), Code::In);
+            let e = client
+                .post_timerecord(&"8b6ee646a9620b28".into(), &req)
+                .await
+                .unwrap_err();
+            assert!(
+                e.to_string().starts_with(
+                    "daily-workings/timerecord/{employeeKey} (employee 8b6ee646..., 2016-05-01 to 2016-05-01): 404"
+                ),
+                "{}",
+                e
+            );
+            assert!(e.is_not_found());
+
+            let e = client
+                .timerecords(&["a".into(), "b".into()], date, date)
+                .await
+                .unwrap_err();
+            assert!(
+                e.to_string().starts_with(
+                    "daily-workings/timerecord (employee a and 1 more, 2016-05-01 to 2016-05-01): 404"
+                ),
+                "{}",
+                e
+            );
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn expose_response_headers() {
+            use crate::mock::FakeTransport;
+            use crate::HttpResponse;
+
+            let transport = FakeTransport::new(|_| {
+                let mut resp = HttpResponse {
+                    status: http::StatusCode::OK,
+                    headers: Default::default(),
+                    body: b"[]".to_vec(),
+                };
+                resp.headers
+                    .insert("x-ratelimit-remaining", "42".parse().unwrap());
+                resp
+            });
+            let client = Client::with_transport(transport, "token");
+            let date = "2016-05-01".parse().unwrap();
+            let envelope = client
+                .timerecords_with_meta(&["a".into()], date, date)
+                .await
+                .unwrap();
+            assert_eq!(envelope.status(), http::StatusCode::OK);
+            assert_eq!(envelope.headers()["x-ratelimit-remaining"], "42");
+            assert!(envelope.data().0.is_empty());
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn get_many_in_chunks() {
+            use crate::mock::{json, FakeTransport};
+            use std::time::Duration;
+
+            // every employee worked on both days; "k" makes its chunk fail
+            let transport = FakeTransport::new(|req| {
+                let (_, keys) = req
+                    .url
+                    .query_pairs()
+                    .find(|(name, _)| name == "employeeKeys")
+                    .unwrap();
+                if keys.split(',').any(|key| key == "k") {
+                    return json(500, r#"{"errors": [{"message": "", "code": 100}]}"#);
+                }
+                let day = |date| {
+                    let workings: Vec<_> = keys
+                        .split(',')
+                        .map(|key| {
+                            format!(
+                                r#"{{"date": "{}", "employeeKey": "{}", "timeRecord": []}}"#,
+                                date, key
+                            )
+                        })
+                        .collect();
+                    format!(
+                        r#"{{"date": "{}", "dailyWorkings": [{}]}}"#,
+                        date,
+                        workings.join(",")
+                    )
+                };
+                json(
+                    200,
+                    &format!("[{}, {}]", day("2016-05-02"), day("2016-05-01")),
+                )
+            })
+            .delay(Duration::from_millis(20));
+            let client = Client::with_transport(transport.clone(), "token");
+
+            let mut keys: Vec<EmployeeKey> = (0..35).map(|i| i.to_string().into()).collect();
+            keys[12] = "k".into();
+            let date = "2016-05-01".parse().unwrap();
+            let many = client
+                .timerecords_many(&keys, date, date.succ_opt().unwrap(), 2)
+                .await;
+
+            assert_eq!(transport.requests().len(), 4);
+            assert_eq!(transport.max_in_flight(), 2);
+
+            assert_eq!(many.failures.len(), 1);
+            assert_eq!(many.failures[0].keys, &keys[10..20]);
+            let Response(days) = many.response;
+            let dates: Vec<_> = days.iter().map(|day| day.date.to_string()).collect();
+            assert_eq!(dates, ["2016-05-01", "2016-05-02"]);
+            for day in &days {
+                let employees: Vec<_> = day
+                    .daily_workings
+                    .iter()
+                    .map(|working| working.employee_key.clone())
+                    .collect();
+                assert_eq!(employees, [&keys[..10], &keys[20..]].concat());
+            }
+        }
+    }
+}
+
+pub mod daily_schedules {
+    use super::{endpoint, Client, Endpoint, ErrorContext, Result};
+    use crate::daily_workings::DivisionBasis;
+    use crate::EmployeeKey;
+    use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+    use serde::de::IgnoredAny;
+    use serde::{Deserialize, Serialize};
+
+    /// The planned schedules of the days and employees picked by `query`.
+    pub async fn get(access_token: &str, query: &Query) -> Result<Response> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .daily_schedules(query)
+            .await
+    }
+
+    /// The planned schedules of `date`, for the employees picked by
+    /// `query`. [`Query::start`] and [`Query::end`] are left out, the date
+    /// being part of the path. Employees without a schedule on the day are
+    /// missing from the result.
+    pub async fn get_by_date(
+        access_token: &str,
+        date: NaiveDate,
+        query: &Query,
+    ) -> Result<Vec<DailySchedule>> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .daily_schedules_on(date, query)
+            .await
+    }
+
+    /// Registers the schedule of the employee `key` on `date`.
+    pub async fn post(
+        access_token: &str,
+        date: NaiveDate,
+        key: &EmployeeKey,
+        req: &ScheduleRequest,
+    ) -> Result<()> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .post_daily_schedule(date, key, req)
+            .await
+    }
+
+    /// Changes the schedule of the employee `key` on `date`, leaving the
+    /// fields that are `None` in `req` as they are.
+    pub async fn update(
+        access_token: &str,
+        date: NaiveDate,
+        key: &EmployeeKey,
+        req: &ScheduleRequest,
+    ) -> Result<()> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .update_daily_schedule(date, key, req)
+            .await
+    }
+
+    impl Client {
+        /// The planned schedules of the days and employees picked by
+        /// `query`. A `start` after `end` fails with
+        /// [`Error::Builder`](crate::Error::Builder) without sending
+        /// anything.
+        pub async fn daily_schedules(&self, query: &Query) -> Result<Response> {
+            let mut context = ErrorContext::new(Endpoint::DailySchedules);
+            if let (Some(start), Some(end)) = (query.start, query.end) {
+                context = context.dates(start, end);
+            }
+            let res = match query.query_string() {
+                Ok(query) => self.get_with_query(endpoint::DAILY_SCHEDULES, &query).await,
+                Err(e) => Err(e),
+            };
+            res.map_err(|e| e.with_context(context))
+        }
+
+        /// Like [`Client::daily_schedules`], for `date` whatever
+        /// [`Query::start`] and [`Query::end`] say.
+        pub async fn daily_schedules_on(
+            &self,
+            date: NaiveDate,
+            query: &Query,
+        ) -> Result<Vec<DailySchedule>> {
+            let query = Query {
+                start: None,
+                end: None,
+                ..query.clone()
+            };
+            let res: Result<DailySchedules> = match query.query_string() {
+                Ok(query) => {
+                    self.get_with_query(&endpoint::daily_schedules_on(date), &query)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            res.map(|day| day.daily_schedules).map_err(|e| {
+                e.with_context(ErrorContext::new(Endpoint::DailySchedulesOfDay).dates(date, date))
+            })
+        }
+
+        /// Registers the schedule of the employee `key` on `date`. One the
+        /// API refuses, e.g. overlapping another, fails with the codes it
+        /// gave in [`Error::api_errors`](crate::Error::api_errors).
+        pub async fn post_daily_schedule(
+            &self,
+            date: NaiveDate,
+            key: &EmployeeKey,
+            req: &ScheduleRequest,
+        ) -> Result<()> {
+            let body = Body { date, req };
+            let IgnoredAny = self
+                .post(&endpoint::daily_schedule(key.as_str())?, &body)
+                .await
+                .map_err(|e| {
+                    e.with_context(
+                        ErrorContext::new(Endpoint::EmployeeDailySchedule)
+                            .employee(key.as_str())
+                            .dates(date, date),
+                    )
+                })?;
+            Ok(())
+        }
+
+        /// Changes the schedule of the employee `key` on `date`, leaving
+        /// the fields that are `None` in `req` as they are.
+        pub async fn update_daily_schedule(
+            &self,
+            date: NaiveDate,
+            key: &EmployeeKey,
+            req: &ScheduleRequest,
+        ) -> Result<()> {
+            let body = Body { date, req };
+            let IgnoredAny = self
+                .put(&endpoint::daily_schedule(key.as_str())?, &body)
+                .await
+                .map_err(|e| {
+                    e.with_context(
+                        ErrorContext::new(Endpoint::EmployeeDailySchedule)
+                            .employee(key.as_str())
+                            .dates(date, date),
+                    )
+                })?;
+            Ok(())
+        }
+    }
+
+    /// Filters of [`get`]. By default the API picks the days.
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct Query {
+        /// The first day, included.
+        pub start: Option<NaiveDate>,
+        /// The last day, included.
+        pub end: Option<NaiveDate>,
+        /// Only the schedules of the division with this code.
+        pub division: Option<String>,
+        /// Which schedules count as `division`'s.
+        pub ondivision: DivisionBasis,
+        /// Only these employees; all of them if empty.
+        pub employee_keys: Vec<EmployeeKey>,
+    }
+
+    impl Query {
+        fn query_string(&self) -> Result<QueryString<'_>> {
+            if let (Some(start), Some(end)) = (self.start, self.end) {
+                if start > end {
+                    return Err(crate::Error::Builder(format!(
+                        "start date {} is after end date {}",
+                        start, end
+                    )));
+                }
+            }
+            Ok(QueryString {
+                start: self.start,
+                end: self.end,
+                division: self.division.as_deref(),
+                ondivision: self.ondivision.query(),
+                employee_keys: Some(self.employee_keys.join(",")).filter(|keys| !keys.is_empty()),
+            })
+        }
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct QueryString<'a> {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start: Option<NaiveDate>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        end: Option<NaiveDate>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        division: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none", rename = "ondivision")]
+        ondivision: Option<bool>,
+        // comma separated
+        #[serde(skip_serializing_if = "Option::is_none")]
+        employee_keys: Option<String>,
+    }
+
+    /// The schedule registered by [`post`] or changed by [`update`]; the
+    /// fields left `None` aren't sent. The times are sent in JST, the only
+    /// offset the API accepts.
+    #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ScheduleRequest {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub schedule_type_code: Option<String>,
+        #[serde(
+            with = "crate::ts_seconds_jst::option",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub clock_in_schedule: Option<DateTime<Utc>>,
+        #[serde(
+            with = "crate::ts_seconds_jst::option",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub clock_out_schedule: Option<DateTime<Utc>>,
+        /// `Some(vec![])` removes the planned breaks.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub break_schedules: Option<Vec<BreakPlan>>,
+    }
+
+    /// A break planned in a [`ScheduleRequest`].
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BreakPlan {
+        #[serde(rename = "breakStartSchedule", with = "crate::ts_seconds_jst")]
+        pub start: DateTime<Utc>,
+        #[serde(rename = "breakEndSchedule", with = "crate::ts_seconds_jst")]
+        pub end: DateTime<Utc>,
+    }
+
+    #[derive(Serialize)]
+    struct Body<'a> {
+        date: NaiveDate,
+        #[serde(flatten)]
+        req: &'a ScheduleRequest,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    pub struct Response(pub Vec<DailySchedules>);
+
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DailySchedules {
+        pub date: NaiveDate,
+        pub daily_schedules: Vec<DailySchedule>,
+    }
+
+    /// What an employee is planned to do on a day. The times keep the
+    /// offset the API sent them with.
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DailySchedule {
+        pub date: NaiveDate,
+        pub employee_key: EmployeeKey,
+        pub schedule_type_code: Option<String>,
+        pub schedule_type_name: Option<String>,
+        /// `None` on days off.
+        pub clock_in_schedule: Option<DateTime<FixedOffset>>,
+        /// `None` on days off.
+        pub clock_out_schedule: Option<DateTime<FixedOffset>>,
+        #[serde(default)]
+        pub break_schedules: Vec<BreakSchedule>,
+    }
+
+    /// A planned break.
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BreakSchedule {
+        pub break_start_schedule: Option<DateTime<FixedOffset>>,
+        pub break_end_schedule: Option<DateTime<FixedOffset>>,
+    }
+
+    #[test]
+    fn serialize_query() {
+        let query = Query {
+            start: NaiveDate::from_ymd_opt(2016, 5, 1),
+            end: NaiveDate::from_ymd_opt(2016, 5, 31),
+            division: Some("1000".into()),
+            ondivision: DivisionBasis::Occurrence,
+            employee_keys: vec!["a".into(), "b".into()],
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string().unwrap()).unwrap(),
+            "start=2016-05-01&end=2016-05-31&division=1000&ondivision=true&employeeKeys=a%2Cb"
+        );
+        assert_eq!(
+            serde_urlencoded::to_string(Query::default().query_string().unwrap()).unwrap(),
+            ""
+        );
+        let query = Query {
+            start: query.end,
+            end: query.start,
+            ..query
+        };
+        assert!(query.query_string().is_err());
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let ex = r##"
+[
+  {
+    "date": "2016-05-01",
+    "dailySchedules": [
+      {
+        "date": "2016-05-01",
+        "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+        "scheduleTypeCode": "1",
+        "scheduleTypeName": "日勤",
+        "clockInSchedule": "2016-05-01T09:00:00+09:00",
+        "clockOutSchedule": "2016-05-01T18:00:00+09:00",
+        "breakSchedules": [
+          {
+            "breakStartSchedule": "2016-05-01T12:00:00+09:00",
+            "breakEndSchedule": "2016-05-01T13:00:00+09:00"
+          }
+        ]
+      },
+      {
+        "date": "2016-05-01",
+        "employeeKey": "c0ffee3bd9b27e0deb9f294a03e3a1b0e5c8ea8fa8372ce72f1a3ca1c3f7e9d2",
+        "scheduleTypeCode": null,
+        "scheduleTypeName": null,
+        "clockInSchedule": null,
+        "clockOutSchedule": null,
+        "breakSchedules": []
+      }
+    ]
+  }
+]
+        "##;
+
+        let Response(days) = serde_json::from_str(ex).unwrap();
+        let schedules = &days[0].daily_schedules;
+        let shift = &schedules[0];
+        let clock_in = shift.clock_in_schedule.unwrap();
+        assert_eq!(clock_in.to_rfc3339(), "2016-05-01T09:00:00+09:00");
+        assert_eq!(clock_in.offset().local_minus_utc(), 9 * 3600);
+        assert_eq!(shift.schedule_type_name.as_deref(), Some("日勤"));
+        assert_eq!(
+            shift.break_schedules[0]
+                .break_end_schedule
+                .unwrap()
+                .to_rfc3339(),
+            "2016-05-01T13:00:00+09:00"
+        );
+
+        let day_off = &schedules[1];
+        assert!(day_off.schedule_type_code.is_none());
+        assert!(day_off.clock_in_schedule.is_none());
+        assert!(day_off.clock_out_schedule.is_none());
+        assert!(day_off.break_schedules.is_empty());
+    }
+
+    #[test]
+    fn serialize_request() {
+        let req = ScheduleRequest {
+            schedule_type_code: Some("1".into()),
+            clock_in_schedule: Some("2016-05-01T00:00:00Z".parse().unwrap()),
+            clock_out_schedule: Some("2016-05-01T09:00:00Z".parse().unwrap()),
+            break_schedules: Some(vec![BreakPlan {
+                start: "2016-05-01T12:00:00.250+09:00".parse().unwrap(),
+                end: "2016-05-01T13:00:00+09:00".parse().unwrap(),
+            }]),
+        };
+        let body = Body {
+            date: "2016-05-01".parse().unwrap(),
+            req: &req,
+        };
+
+        let json = r##"
+        {
+            "date": "2016-05-01",
+            "scheduleTypeCode": "1",
+            "clockInSchedule": "2016-05-01T09:00:00+09:00",
+            "clockOutSchedule": "2016-05-01T18:00:00+09:00",
+            "breakSchedules": [
+                {
+                    "breakStartSchedule": "2016-05-01T12:00:00+09:00",
+                    "breakEndSchedule": "2016-05-01T13:00:00+09:00"
+                }
+            ]
+        }
+        "##;
+
+        let v1 = serde_json::from_str::<serde_json::Value>(json).unwrap();
+        let v2 = serde_json::from_str::<serde_json::Value>(&serde_json::to_string(&body).unwrap())
+            .unwrap();
+
+        assert_eq!(v1, v2);
+
+        assert_eq!(
+            serde_json::to_value(ScheduleRequest::default()).unwrap(),
+            serde_json::json!({})
+        );
+        let req = ScheduleRequest {
+            clock_out_schedule: Some("2016-05-01T10:00:00Z".parse().unwrap()),
+            break_schedules: Some(vec![]),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            serde_json::json!({
+                "clockOutSchedule": "2016-05-01T19:00:00+09:00",
+                "breakSchedules": [],
+            })
+        );
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn update_without_content() {
+        use crate::mock::{json, FakeTransport};
+
+        let req = ScheduleRequest {
+            clock_out_schedule: Some("2016-05-01T19:00:00+09:00".parse().unwrap()),
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2016, 5, 1).unwrap();
+        for &(status, body) in &[(200, ""), (204, ""), (200, "{}")] {
+            let transport = FakeTransport::new(move |_| json(status, body));
+            let client = Client::with_transport(transport.clone(), "token");
+            let res = client
+                .update_daily_schedule(date, &"8b6ee646".into(), &req)
+                .await;
+            assert!(res.is_ok(), "{} {:?}: {:?}", status, body, res);
+
+            let sent = &transport.requests()[0];
+            assert_eq!(sent.method, http::Method::PUT);
+            assert_eq!(
+                sent.body.as_deref(),
+                Some(
+                    &br#"{"date":"2016-05-01","clockOutSchedule":"2016-05-01T19:00:00+09:00"}"#[..]
+                )
+            );
+        }
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn reject_overlapping_schedule() {
+        use crate::mock::{json, FakeTransport};
+        use crate::ErrorCode;
+
+        let transport = FakeTransport::new(|_| {
+            json(
+                400,
+                r#"{"errors": [{"message": "スケジュールが重複しています", "code": 200}]}"#,
+            )
+        });
+        let client = Client::with_transport(transport.clone(), "token");
+        let req = ScheduleRequest {
+            schedule_type_code: Some("1".into()),
+            clock_in_schedule: Some("2016-05-01T09:00:00+09:00".parse().unwrap()),
+            clock_out_schedule: Some("2016-05-01T18:00:00+09:00".parse().unwrap()),
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2016, 5, 1).unwrap();
+        let e = client
+            .post_daily_schedule(date, &"8b6ee646".into(), &req)
+            .await
+            .unwrap_err();
+        let errors = e.api_errors().unwrap();
+        assert_eq!(errors[0].code(), ErrorCode::InvalidParameter);
+        assert_eq!(errors[0].message, "スケジュールが重複しています");
+
+        let sent = &transport.requests()[0];
+        assert_eq!(sent.method, http::Method::POST);
+        assert_eq!(
+            sent.url.as_str(),
+            "https://api.kingtime.jp/v1.0/daily-schedules/8b6ee646"
+        );
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn get_one_day() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|_| {
+            json(
+                200,
+                r#"{"date": "2016-05-01", "dailySchedules": [{"date": "2016-05-01", "employeeKey": "a", "clockInSchedule": "2016-05-01T09:00:00+09:00", "clockOutSchedule": "2016-05-01T18:00:00+09:00"}]}"#,
+            )
+        });
+        let client = Client::with_transport(transport.clone(), "token");
+        let date = NaiveDate::from_ymd_opt(2016, 5, 1).unwrap();
+        let query = Query {
+            start: NaiveDate::from_ymd_opt(2016, 6, 1),
+            division: Some("1000".into()),
+            ..Default::default()
+        };
+        let schedules = client.daily_schedules_on(date, &query).await.unwrap();
+        assert_eq!(schedules.len(), 1);
+        assert_eq!(schedules[0].employee_key, "a");
+        assert_eq!(
+            schedules[0].clock_out_schedule.unwrap().to_rfc3339(),
+            "2016-05-01T18:00:00+09:00"
+        );
+        assert!(schedules[0].break_schedules.is_empty());
+        assert_eq!(
+            transport.requests()[0].url.as_str(),
+            "https://api.kingtime.jp/v1.0/daily-schedules/2016-05-01?division=1000"
+        );
+    }
+}
+
+pub mod monthly_workings {
+    use super::{endpoint, Client, Endpoint, ErrorContext, Result, YearMonth};
+    use crate::daily_workings::DivisionBasis;
+    use crate::{CurrentDateEmployee, EmployeeKey, Minutes};
+    use serde::{Deserialize, Serialize};
+
+    /// The monthly workings of every employee, by default for the current
+    /// month.
+    pub async fn get(access_token: &str, query: &Query) -> Result<Vec<MonthlyWorking>> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .monthly_workings(query)
+            .await
+    }
+
+    /// The monthly workings of `month`. [`Query::date`] is left out, the
+    /// month being part of the path.
+    pub async fn get_month(
+        access_token: &str,
+        month: YearMonth,
+        query: &Query,
+    ) -> Result<Vec<MonthlyWorking>> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .monthly_workings_of(month, query)
+            .await
+    }
+
+    impl Client {
+        pub async fn monthly_workings(&self, query: &Query) -> Result<Vec<MonthlyWorking>> {
+            let mut context = ErrorContext::new(Endpoint::MonthlyWorkings);
+            if let Some(month) = query.date {
+                context = context.dates(month.first_day(), month.last_day());
+            }
+            self.get_with_query(endpoint::MONTHLY_WORKINGS, &query.query_string())
+                .await
+                .map_err(|e| e.with_context(context))
+        }
+
+        /// Like [`Client::monthly_workings`], for `month` whatever
+        /// [`Query::date`] says.
+        pub async fn monthly_workings_of(
+            &self,
+            month: YearMonth,
+            query: &Query,
+        ) -> Result<Vec<MonthlyWorking>> {
+            let query = QueryString {
+                date: None,
+                ..query.query_string()
+            };
+            self.get_with_query(&endpoint::monthly_workings_of(month), &query)
+                .await
+                .map_err(|e| {
+                    e.with_context(
+                        ErrorContext::new(Endpoint::MonthlyWorkingsOfMonth)
+                            .dates(month.first_day(), month.last_day()),
+                    )
+                })
+        }
+    }
+
+    /// Filters of [`get`] and [`get_month`].
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct Query {
+        /// The month, the current one if not set.
+        pub date: Option<YearMonth>,
+        /// Only the records of the division with this code.
+        pub division: Option<String>,
+        /// Which records count as `division`'s.
+        pub ondivision: DivisionBasis,
+        /// Only these employees; all of them if empty.
+        pub employee_keys: Vec<EmployeeKey>,
+        /// Fields left out of [`MonthlyWorking`] unless asked for.
+        pub additional_fields: Vec<AdditionalField>,
+    }
+
+    impl Query {
+        fn query_string(&self) -> QueryString<'_> {
+            let fields: Vec<_> = self
+                .additional_fields
+                .iter()
+                .map(AdditionalField::as_str)
+                .collect();
+            QueryString {
+                date: self.date,
+                division: self.division.as_deref(),
+                ondivision: self.ondivision.query(),
+                employee_keys: Some(self.employee_keys.join(",")).filter(|keys| !keys.is_empty()),
+                additional_fields: Some(fields.join(",")).filter(|fields| !fields.is_empty()),
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct QueryString<'a> {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        date: Option<YearMonth>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        division: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none", rename = "ondivision")]
+        ondivision: Option<bool>,
+        // comma separated
+        #[serde(skip_serializing_if = "Option::is_none")]
+        employee_keys: Option<String>,
+        // comma separated
+        #[serde(skip_serializing_if = "Option::is_none")]
+        additional_fields: Option<String>,
+    }
+
+    /// A field of [`MonthlyWorking`] left out unless asked for in [`Query`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum AdditionalField {
+        /// The employee as they are at the end of the month.
+        CurrentDateEmployee,
+        /// The custom monthly working items.
+        CustomMonthlyWorkings,
+    }
+
+    impl AdditionalField {
+        /// The name of the field in the API.
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                AdditionalField::CurrentDateEmployee => "currentDateEmployee",
+                AdditionalField::CustomMonthlyWorkings => "customMonthlyWorkings",
+            }
+        }
+    }
+
+    /// The working hours of an employee over a month, in minutes.
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct MonthlyWorking {
+        pub date: YearMonth,
+        pub employee_key: EmployeeKey,
+        pub is_closing: bool,
+        /// Days scheduled as workdays.
+        pub workingday_count: u32,
+        /// Days actually worked.
+        pub working_count: u32,
+        pub absent_count: u32,
+        pub late_count: u32,
+        pub early_leave_count: u32,
+        pub holiday_work_count: u32,
+        pub assigned: Minutes,
+        pub unassigned: Minutes,
+        pub overtime: Minutes,
+        pub late_night: Minutes,
+        pub late_night_unassigned: Minutes,
+        pub late_night_overtime: Minutes,
+        pub break_time: Minutes,
+        pub late: Minutes,
+        pub early_leave: Minutes,
+        pub total_work: Minutes,
+        #[serde(default)]
+        pub holidays_obtained: Vec<HolidayObtained>,
+        /// Only with [`AdditionalField::CurrentDateEmployee`].
+        pub current_date_employee: Option<CurrentDateEmployee>,
+        /// Only with [`AdditionalField::CustomMonthlyWorkings`].
+        #[serde(default)]
+        pub custom_monthly_workings: Vec<CustomMonthlyWorking>,
+    }
+
+    /// The value of a custom monthly working item.
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CustomMonthlyWorking {
+        pub code: String,
+        pub name: String,
+        #[serde(rename = "calculationUnitCode")]
+        pub calculation_unit: crate::CalculationUnit,
+        pub calculation_result: f64,
+    }
+
+    /// The holidays of a kind taken in a month.
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct HolidayObtained {
+        pub code: u32,
+        pub name: String,
+        /// Full days, with half days counting as 0.5.
+        pub day_count: f64,
+        /// Hourly holidays.
+        pub minutes: Minutes,
+    }
+
+    #[test]
+    fn serialize_query() {
+        let query = Query {
+            date: YearMonth::new(2016, 5),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string()).unwrap(),
+            "date=2016-05"
+        );
+        let query = Query {
+            division: Some("1000".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string()).unwrap(),
+            "division=1000"
+        );
+        let query = Query {
+            ondivision: DivisionBasis::Occurrence,
+            ..query
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string()).unwrap(),
+            "division=1000&ondivision=true"
+        );
+        let query = Query {
+            employee_keys: vec!["a".into(), "b".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string()).unwrap(),
+            "employeeKeys=a%2Cb"
+        );
+        let query = Query {
+            additional_fields: vec![
+                AdditionalField::CurrentDateEmployee,
+                AdditionalField::CustomMonthlyWorkings,
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string()).unwrap(),
+            "additionalFields=currentDateEmployee%2CcustomMonthlyWorkings"
+        );
+        let query = Query {
+            employee_keys: vec![],
+            additional_fields: vec![],
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string()).unwrap(),
+            ""
+        );
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn get_one_month() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|_| json(200, "[]"));
+        let client = Client::with_transport(transport.clone(), "token");
+        let december = YearMonth::new(2016, 12).unwrap();
+        let query = Query {
+            date: Some(december),
+            division: Some("1000".into()),
+            employee_keys: vec!["a".into(), "b".into()],
+            ..Default::default()
+        };
+        client.monthly_workings_of(december, &query).await.unwrap();
+        let january = december.succ_opt().unwrap();
+        client
+            .monthly_workings_of(january, &Query::default())
+            .await
+            .unwrap();
+
+        let urls: Vec<_> = transport
+            .requests()
+            .iter()
+            .map(|req| req.url.to_string())
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://api.kingtime.jp/v1.0/monthly-workings/2016-12?division=1000&employeeKeys=a%2Cb",
+                "https://api.kingtime.jp/v1.0/monthly-workings/2017-01",
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let ex = r##"
+[
+  {
+    "date": "2016-05",
+    "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+    "isClosing": true,
+    "workingdayCount": 21,
+    "workingCount": 20,
+    "absentCount": 1,
+    "lateCount": 2,
+    "earlyLeaveCount": 0,
+    "holidayWorkCount": 1,
+    "assigned": 9600,
+    "unassigned": 1320,
+    "overtime": 1320,
+    "lateNight": 90,
+    "lateNightUnassigned": 0,
+    "lateNightOvertime": 90,
+    "breakTime": 1200,
+    "late": 25,
+    "earlyLeave": 0,
+    "totalWork": 10920,
+    "holidaysObtained": [
+      {
+        "code": 1,
+        "name": "有休",
+        "dayCount": 1.5,
+        "minutes": 60
+      },
+      {
+        "code": 2,
+        "name": "代休",
+        "dayCount": 1,
+        "minutes": 0
+      }
+    ]
+  },
+  {
+    "date": "2016-05",
+    "employeeKey": "c0ffee3bd9b27e0deb9f294a03e3a1b0e5c8ea8fa8372ce72f1a3ca1c3f7e9d2",
+    "isClosing": false,
+    "workingdayCount": 21,
+    "workingCount": 21,
+    "absentCount": 0,
+    "lateCount": 0,
+    "earlyLeaveCount": 0,
+    "holidayWorkCount": 0,
+    "assigned": 10080,
+    "unassigned": 0,
+    "overtime": 0,
+    "lateNight": 0,
+    "lateNightUnassigned": 0,
+    "lateNightOvertime": 0,
+    "breakTime": 1260,
+    "late": 0,
+    "earlyLeave": 0,
+    "totalWork": 10080,
+    "currentDateEmployee": {
+      "code": "2000",
+      "lastName": "勤怠",
+      "firstName": "花子",
+      "divisionCode": "2000"
+    },
+    "customMonthlyWorkings": [
+      {
+        "code": "mCus1",
+        "name": "月別カスタム1",
+        "calculationUnitCode": 2,
+        "calculationResult": 20
+      }
+    ]
+  }
+]
+        "##;
+
+        let workings: Vec<MonthlyWorking> = serde_json::from_str(ex).unwrap();
+        assert_eq!(workings.len(), 2);
+        let mw = &workings[0];
+        assert_eq!(mw.date, YearMonth::new(2016, 5).unwrap());
+        assert_eq!(mw.total_work, Minutes(10920));
+        assert_eq!(mw.total_work.to_string(), "182h 0m");
+        assert_eq!(mw.overtime, Minutes(1320));
+        assert_eq!(mw.holidays_obtained[0].day_count, 1.5);
+        assert!(workings[1].holidays_obtained.is_empty());
+        assert!(mw.current_date_employee.is_none());
+        assert!(mw.custom_monthly_workings.is_empty());
+        let employee = workings[1].current_date_employee.as_ref().unwrap();
+        assert_eq!(employee.code, "2000");
+        assert_eq!(workings[1].custom_monthly_workings[0].code, "mCus1");
+        assert_eq!(
+            workings[1].custom_monthly_workings[0].calculation_unit,
+            crate::CalculationUnit::Times
+        );
+    }
+}
+
+pub mod monthly_schedules {
+    use super::{endpoint, Client, Endpoint, ErrorContext, Result, YearMonth};
+    use crate::EmployeeKey;
+    use serde::{Deserialize, Serialize};
+
+    /// The monthly schedules of every employee, by default for the current
+    /// month.
+    pub async fn get(access_token: &str, query: &Query) -> Result<Vec<MonthlySchedule>> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .monthly_schedules(query)
+            .await
+    }
+
+    impl Client {
+        pub async fn monthly_schedules(&self, query: &Query) -> Result<Vec<MonthlySchedule>> {
+            let mut context = ErrorContext::new(Endpoint::MonthlySchedules);
+            if let Some(month) = query.date {
+                context = context.dates(month.first_day(), month.last_day());
+            }
+            self.get_with_query(endpoint::MONTHLY_SCHEDULES, &query.query_string())
+                .await
+                .map_err(|e| e.with_context(context))
+        }
+    }
+
+    /// Filters of [`get`].
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct Query {
+        /// The month, the current one if not set.
+        pub date: Option<YearMonth>,
+        /// Only the employees of the division with this code.
+        pub division: Option<String>,
+        /// Only these employees; all of them if empty.
+        pub employee_keys: Vec<EmployeeKey>,
+    }
+
+    impl Query {
+        fn query_string(&self) -> QueryString<'_> {
+            QueryString {
+                date: self.date,
+                division: self.division.as_deref(),
+                employee_keys: Some(self.employee_keys.join(",")).filter(|keys| !keys.is_empty()),
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct QueryString<'a> {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        date: Option<YearMonth>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        division: Option<&'a str>,
+        // comma separated
+        #[serde(skip_serializing_if = "Option::is_none")]
+        employee_keys: Option<String>,
+    }
+
+    /// What an employee is scheduled to work over a month, in minutes.
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct MonthlySchedule {
+        pub date: YearMonth,
+        pub employee_key: EmployeeKey,
+        /// Days scheduled as workdays.
+        pub workingday_count: u32,
+        pub scheduled_minutes: u32,
+        #[serde(default)]
+        pub holidays_planned: Vec<PlannedHoliday>,
+    }
+
+    /// The holidays of a kind planned in a month.
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PlannedHoliday {
+        pub code: u32,
+        pub name: String,
+        /// Full days, with half days counting as 0.5.
+        pub day_count: f64,
+    }
+
+    #[test]
+    fn serialize_query() {
+        let query = Query {
+            date: YearMonth::new(2016, 5),
+            division: Some("1000".into()),
+            employee_keys: vec!["a".into(), "b".into()],
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string()).unwrap(),
+            "date=2016-05&division=1000&employeeKeys=a%2Cb"
+        );
+        let query = Query {
+            division: Some("1000".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string()).unwrap(),
+            "division=1000"
+        );
+        let query = Query {
+            employee_keys: vec!["a".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string()).unwrap(),
+            "employeeKeys=a"
+        );
+        assert_eq!(
+            serde_urlencoded::to_string(Query::default().query_string()).unwrap(),
+            ""
+        );
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let ex = r##"
+[
+  {
+    "date": "2016-05",
+    "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+    "workingdayCount": 21,
+    "scheduledMinutes": 10080,
+    "holidaysPlanned": [
+      {
+        "code": 1,
+        "name": "有休",
+        "dayCount": 0.5
+      }
+    ]
+  },
+  {
+    "date": "2016-05",
+    "employeeKey": "c0ffee3bd9b27e0deb9f294a03e3a1b0e5c8ea8fa8372ce72f1a3ca1c3f7e9d2",
+    "workingdayCount": 0,
+    "scheduledMinutes": 0
+  }
+]
+        "##;
+
+        let schedules: Vec<MonthlySchedule> = serde_json::from_str(ex).unwrap();
+        assert_eq!(schedules[0].date, YearMonth::new(2016, 5).unwrap());
+        assert_eq!(schedules[0].workingday_count, 21);
+        assert_eq!(schedules[0].scheduled_minutes, 10080);
+        assert_eq!(schedules[0].holidays_planned[0].day_count, 0.5);
+        assert!(schedules[1].holidays_planned.is_empty());
+    }
+}
+
+pub mod requests {
+    use crate::{Client, Endpoint};
+    use chrono::NaiveDate;
+    use serde::de::IgnoredAny;
+    use serde::{Deserialize, Serialize};
+
+    /// Where an application is in its approval route.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+    #[serde(from = "String")]
+    pub enum Status {
+        Applying,
+        Approved,
+        Rejected,
+        Canceled,
+        /// A status this version of the crate does not know about.
+        Unknown(String),
+    }
+
+    impl Status {
+        /// The name of the status in the API.
+        pub fn as_str(&self) -> &str {
+            match self {
+                Status::Applying => "applying",
+                Status::Approved => "approved",
+                Status::Rejected => "rejected",
+                Status::Canceled => "canceled",
+                Status::Unknown(status) => status,
+            }
+        }
+    }
+
+    impl From<String> for Status {
+        fn from(status: String) -> Self {
+            match &*status {
+                "applying" => Status::Applying,
+                "approved" => Status::Approved,
+                "rejected" => Status::Rejected,
+                "canceled" => Status::Canceled,
+                _ => Status::Unknown(status),
+            }
+        }
+    }
+
+    impl Serialize for Status {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(self.as_str())
+        }
+    }
+
+    #[test]
+    fn round_trip_statuses() {
+        for status in &["applying", "approved", "rejected", "canceled", "withdrawn"] {
+            let json = serde_json::to_string(status).unwrap();
+            let parsed: Status = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed.as_str(), *status);
+            assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+        }
+    }
+
+    /// One approval an application goes through.
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ApprovalStep {
+        /// The key of the administrator deciding at this step.
+        pub administrator_key: Option<String>,
+        pub status: Status,
+    }
+
+    /// What an approver makes of an application.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Decision {
+        Approve,
+        Reject {
+            /// Shown to the applicant.
+            reason: Option<String>,
+        },
+    }
+
+    impl Decision {
+        fn body(&self) -> DecisionBody<'_> {
+            match self {
+                Decision::Approve => DecisionBody {
+                    status: Status::Approved.as_str(),
+                    comment: None,
+                },
+                Decision::Reject { reason } => DecisionBody {
+                    status: Status::Rejected.as_str(),
+                    comment: reason.as_deref(),
+                },
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    struct DecisionBody<'a> {
+        status: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        comment: Option<&'a str>,
+    }
+
+    impl Client {
+        // Decides the application at `path`.
+        async fn decide_request(
+            &self,
+            path: &str,
+            endpoint: Endpoint,
+            decision: &Decision,
+        ) -> crate::Result<()> {
+            let IgnoredAny = self
+                .put(path, &decision.body())
+                .await
+                .map_err(|e| e.with_context(crate::ErrorContext::new(endpoint)))?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn serialize_decision() {
+        let body = |decision: Decision| serde_json::to_value(decision.body()).unwrap();
+        assert_eq!(
+            body(Decision::Approve),
+            serde_json::json!({"status": "approved"})
+        );
+        assert_eq!(
+            body(Decision::Reject { reason: None }),
+            serde_json::json!({"status": "rejected"})
+        );
+        assert_eq!(
+            body(Decision::Reject {
+                reason: Some("上長確認中".into())
+            }),
+            serde_json::json!({"status": "rejected", "comment": "上長確認中"})
+        );
+    }
+
+    /// Filters of the `list` functions. By default the API picks the days.
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct Query {
+        /// The first day applied for, included.
+        pub start: Option<NaiveDate>,
+        /// The last day applied for, included.
+        pub end: Option<NaiveDate>,
+        /// Only the applications in this status.
+        pub status: Option<Status>,
+    }
+
+    impl Query {
+        fn query_string(&self) -> crate::Result<QueryString<'_>> {
+            if let (Some(start), Some(end)) = (self.start, self.end) {
+                if start > end {
+                    return Err(crate::Error::Builder(format!(
+                        "start date {} is after end date {}",
+                        start, end
+                    )));
+                }
+            }
+            Ok(QueryString {
+                start: self.start,
+                end: self.end,
+                status: self.status.as_ref().map(Status::as_str),
+            })
+        }
+
+        fn context(&self, endpoint: crate::Endpoint) -> crate::ErrorContext {
+            let context = crate::ErrorContext::new(endpoint);
+            match (self.start, self.end) {
+                (Some(start), Some(end)) => context.dates(start, end),
+                _ => context,
+            }
+        }
+    }
+
+    #[derive(Serialize)]
+    struct QueryString<'a> {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start: Option<NaiveDate>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        end: Option<NaiveDate>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        status: Option<&'a str>,
+    }
+
+    #[test]
+    fn serialize_query() {
+        let query = Query {
+            start: NaiveDate::from_ymd_opt(2016, 5, 1),
+            end: NaiveDate::from_ymd_opt(2016, 5, 31),
+            status: Some(Status::Applying),
+        };
+        assert_eq!(
+            serde_urlencoded::to_string(query.query_string().unwrap()).unwrap(),
+            "start=2016-05-01&end=2016-05-31&status=applying"
+        );
+        assert_eq!(
+            serde_urlencoded::to_string(Query::default().query_string().unwrap()).unwrap(),
+            ""
+        );
+        let query = Query {
+            start: query.end,
+            end: query.start,
+            ..query
+        };
+        assert!(query.query_string().is_err());
+    }
+
+    #[test]
+    fn deserialize_status() {
+        let statuses: Vec<Status> =
+            serde_json::from_str(r#"["applying", "approved", "rejected", "canceled", "remanded"]"#)
+                .unwrap();
+        assert_eq!(
+            statuses,
+            [
+                Status::Applying,
+                Status::Approved,
+                Status::Rejected,
+                Status::Canceled,
+                Status::Unknown("remanded".into()),
+            ]
+        );
+        assert_eq!(statuses[4].as_str(), "remanded");
+    }
+
+    pub mod overtime {
+        use super::{Decision, Query, Status};
+        use crate::{endpoint, Client, EmployeeKey, Endpoint, Result};
+        use chrono::{DateTime, FixedOffset, NaiveDate};
+        use serde::{Deserialize, Serialize};
+
+        /// The overtime applications picked by `query`.
+        pub async fn list(access_token: &str, query: &Query) -> Result<Vec<OvertimeRequest>> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .overtime_requests(query)
+                .await
+        }
+
+        /// Approves or rejects the overtime application `request_key`.
+        pub async fn decide(
+            access_token: &str,
+            request_key: &str,
+            decision: Decision,
+        ) -> Result<()> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .decide_overtime_request(request_key, decision)
+                .await
+        }
+
+        impl Client {
+            /// The overtime applications picked by `query`. A `start`
+            /// after `end` fails with [`Error::Builder`](crate::Error::Builder)
+            /// without sending anything.
+            pub async fn overtime_requests(&self, query: &Query) -> Result<Vec<OvertimeRequest>> {
+                let res = match query.query_string() {
+                    Ok(q) => self.get_with_query(endpoint::OVERTIME_REQUESTS, &q).await,
+                    Err(e) => Err(e),
+                };
+                res.map_err(|e| e.with_context(query.context(Endpoint::OvertimeRequests)))
+            }
+
+            /// Approves or rejects the overtime application `request_key`.
+            /// Deciding one that is already decided fails with the error
+            /// codes the API gave in [`Error::api_errors`](crate::Error::api_errors).
+            pub async fn decide_overtime_request(
+                &self,
+                request_key: &str,
+                decision: Decision,
+            ) -> Result<()> {
+                self.decide_request(
+                    &endpoint::overtime_request(request_key)?,
+                    Endpoint::OvertimeRequest,
+                    &decision,
+                )
+                .await
+            }
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn decide_through_transport() {
+            use crate::mock::{json, FakeTransport};
+            use crate::ErrorCode;
+
+            let transport = FakeTransport::new(|req| {
+                if req.url.path().ends_with("/done") {
+                    json(
+                        400,
+                        r#"{"errors": [{"message": "既に承認済みです", "code": 200}]}"#,
+                    )
+                } else {
+                    json(204, "")
+                }
+            });
+            let client = Client::with_transport(transport.clone(), "token");
+            client
+                .decide_overtime_request("5a6c0e2f", Decision::Approve)
+                .await
+                .unwrap();
+            let sent = &transport.requests()[0];
+            assert_eq!(sent.method, http::Method::PUT);
+            assert_eq!(
+                sent.url.as_str(),
+                "https://api.kingtime.jp/v1.0/requests/overtime/5a6c0e2f"
+            );
+            assert_eq!(sent.body.as_deref(), Some(&br#"{"status":"approved"}"#[..]));
+
+            let e = client
+                .decide_overtime_request("done", Decision::Reject { reason: None })
+                .await
+                .unwrap_err();
+            let errors = e.api_errors().unwrap();
+            assert_eq!(errors[0].code(), ErrorCode::InvalidParameter);
+            assert_eq!(errors[0].message, "既に承認済みです");
+        }
+
+        /// An application for overtime on a day, either as a number of
+        /// minutes or as a time range.
+        #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct OvertimeRequest {
+            pub request_key: String,
+            pub employee_key: EmployeeKey,
+            pub date: NaiveDate,
+            pub minutes: Option<u32>,
+            pub start: Option<DateTime<FixedOffset>>,
+            pub end: Option<DateTime<FixedOffset>>,
+            pub status: Status,
+            /// What the applicant wrote.
+            pub comment: Option<String>,
+        }
+
+        #[test]
+        fn deserialize_list() {
+            let ex = r##"
+[
+  {
+    "requestKey": "5a6c0e2f7b5e4f8a9d1b3c4e6f708192",
+    "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+    "date": "2016-05-01",
+    "minutes": null,
+    "start": "2016-05-01T18:00:00+09:00",
+    "end": "2016-05-01T20:30:00+09:00",
+    "status": "applying",
+    "comment": "リリース対応"
+  },
+  {
+    "requestKey": "0f1e2d3c4b5a69788796a5b4c3d2e1f0",
+    "employeeKey": "c0ffee3bd9b27e0deb9f294a03e3a1b0e5c8ea8fa8372ce72f1a3ca1c3f7e9d2",
+    "date": "2016-05-02",
+    "minutes": 60,
+    "status": "approved"
+  }
+]
+            "##;
+
+            let requests: Vec<OvertimeRequest> = serde_json::from_str(ex).unwrap();
+            let pending = &requests[0];
+            assert_eq!(pending.status, Status::Applying);
+            assert!(pending.minutes.is_none());
+            assert_eq!(
+                pending.end.unwrap().to_rfc3339(),
+                "2016-05-01T20:30:00+09:00"
+            );
+            assert_eq!(pending.comment.as_deref(), Some("リリース対応"));
+
+            let approved = &requests[1];
+            assert_eq!(approved.status, Status::Approved);
+            assert_eq!(approved.minutes, Some(60));
+            assert!(approved.start.is_none());
+            assert!(approved.comment.is_none());
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn list_through_transport() {
+            use crate::mock::{json, FakeTransport};
+
+            let transport = FakeTransport::new(|_| json(200, "[]"));
+            let client = Client::with_transport(transport.clone(), "token");
+            let query = Query {
+                status: Some(Status::Applying),
+                ..Default::default()
+            };
+            assert!(client.overtime_requests(&query).await.unwrap().is_empty());
+            assert_eq!(
+                transport.requests()[0].url.as_str(),
+                "https://api.kingtime.jp/v1.0/requests/overtime?status=applying"
+            );
+        }
+    }
+
+    pub mod schedule {
+        use super::{ApprovalStep, Decision, Query, Status};
+        use crate::daily_schedules::BreakSchedule;
+        use crate::{endpoint, Client, EmployeeKey, Endpoint, Result};
+        use chrono::{DateTime, FixedOffset, NaiveDate};
+        use serde::{Deserialize, Serialize};
+
+        /// The schedule change applications picked by `query`.
+        pub async fn list(access_token: &str, query: &Query) -> Result<Vec<ScheduleRequest>> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .schedule_requests(query)
+                .await
+        }
+
+        /// Approves or rejects the schedule change application
+        /// `request_key`.
+        pub async fn decide(
+            access_token: &str,
+            request_key: &str,
+            decision: Decision,
+        ) -> Result<()> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .decide_schedule_request(request_key, decision)
+                .await
+        }
+
+        impl Client {
+            /// The schedule change applications picked by `query`. A
+            /// `start` after `end` fails with
+            /// [`Error::Builder`](crate::Error::Builder) without sending
+            /// anything.
+            pub async fn schedule_requests(&self, query: &Query) -> Result<Vec<ScheduleRequest>> {
+                let res = match query.query_string() {
+                    Ok(q) => self.get_with_query(endpoint::SCHEDULE_REQUESTS, &q).await,
+                    Err(e) => Err(e),
                 };
-                Ok(c)
+                res.map_err(|e| e.with_context(query.context(Endpoint::ScheduleRequests)))
+            }
+
+            /// Approves or rejects the schedule change application
+            /// `request_key`. One the applicant has withdrawn in the
+            /// meantime fails with [`ErrorCode::RequestWithdrawn`](crate::ErrorCode::RequestWithdrawn).
+            pub async fn decide_schedule_request(
+                &self,
+                request_key: &str,
+                decision: Decision,
+            ) -> Result<()> {
+                self.decide_request(
+                    &endpoint::schedule_request(request_key)?,
+                    Endpoint::ScheduleRequest,
+                    &decision,
+                )
+                .await
             }
         }
 
-        impl<'de> Deserialize<'de> for Code {
-            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
-            where
-                D: serde::Deserializer<'de>,
-            {
-                deserializer.deserialize_str(CodeVisitor)
+        /// An application for changing the schedule of a day.
+        #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct ScheduleRequest {
+            pub request_key: String,
+            pub employee_key: EmployeeKey,
+            pub date: NaiveDate,
+            pub status: Status,
+            /// The schedule applied for.
+            pub requested: RequestedSchedule,
+            /// The approvals the application goes through, in order.
+            #[serde(default)]
+            pub approval_route: Vec<ApprovalStep>,
+            /// What the applicant wrote.
+            pub comment: Option<String>,
+        }
+
+        /// The schedule a [`ScheduleRequest`] asks for. The times keep the
+        /// offset the API sent them with.
+        #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct RequestedSchedule {
+            pub schedule_type_code: Option<String>,
+            pub schedule_type_name: Option<String>,
+            pub clock_in_schedule: Option<DateTime<FixedOffset>>,
+            pub clock_out_schedule: Option<DateTime<FixedOffset>>,
+            #[serde(default)]
+            pub break_schedules: Vec<BreakSchedule>,
+        }
+
+        #[test]
+        fn deserialize_list() {
+            let ex = r##"
+[
+  {
+    "requestKey": "5a6c0e2f7b5e4f8a9d1b3c4e6f708192",
+    "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+    "date": "2016-05-01",
+    "status": "applying",
+    "requested": {
+      "scheduleTypeCode": "2",
+      "scheduleTypeName": "遅番",
+      "clockInSchedule": "2016-05-01T13:00:00+09:00",
+      "clockOutSchedule": "2016-05-01T22:00:00+09:00",
+      "breakSchedules": [
+        {
+          "breakStartSchedule": "2016-05-01T17:00:00+09:00",
+          "breakEndSchedule": "2016-05-01T18:00:00+09:00"
+        }
+      ]
+    },
+    "approvalRoute": [
+      {
+        "administratorKey": "4d2a8dbb",
+        "status": "approved"
+      },
+      {
+        "administratorKey": "9f3c1e07",
+        "status": "applying"
+      }
+    ],
+    "comment": "シフト交代"
+  },
+  {
+    "requestKey": "0f1e2d3c4b5a69788796a5b4c3d2e1f0",
+    "employeeKey": "c0ffee3bd9b27e0deb9f294a03e3a1b0e5c8ea8fa8372ce72f1a3ca1c3f7e9d2",
+    "date": "2016-05-02",
+    "status": "remanded",
+    "requested": {
+      "scheduleTypeCode": null,
+      "clockInSchedule": null,
+      "clockOutSchedule": null
+    }
+  }
+]
+            "##;
+
+            let requests: Vec<ScheduleRequest> = serde_json::from_str(ex).unwrap();
+            let swap = &requests[0];
+            assert_eq!(swap.status, Status::Applying);
+            assert_eq!(swap.requested.schedule_type_name.as_deref(), Some("遅番"));
+            assert_eq!(
+                swap.requested.clock_in_schedule.unwrap().to_rfc3339(),
+                "2016-05-01T13:00:00+09:00"
+            );
+            assert_eq!(swap.requested.break_schedules.len(), 1);
+            let route: Vec<_> = swap
+                .approval_route
+                .iter()
+                .map(|step| step.status.clone())
+                .collect();
+            assert_eq!(route, [Status::Approved, Status::Applying]);
+
+            let other = &requests[1];
+            assert_eq!(other.status, Status::Unknown("remanded".into()));
+            assert!(other.requested.clock_in_schedule.is_none());
+            assert!(other.approval_route.is_empty());
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn decide_through_transport() {
+            use crate::mock::{json, FakeTransport};
+            use crate::ErrorCode;
+
+            let transport = FakeTransport::new(|req| {
+                if req.url.path().ends_with("/withdrawn") {
+                    json(
+                        409,
+                        r#"{"errors": [{"message": "申請は取り下げられています", "code": 304}]}"#,
+                    )
+                } else {
+                    json(200, "")
+                }
+            });
+            let client = Client::with_transport(transport.clone(), "token");
+            client
+                .decide_schedule_request("5a6c0e2f", Decision::Approve)
+                .await
+                .unwrap();
+            let sent = &transport.requests()[0];
+            assert_eq!(sent.method, http::Method::PUT);
+            assert_eq!(
+                sent.url.as_str(),
+                "https://api.kingtime.jp/v1.0/requests/schedule/5a6c0e2f"
+            );
+            assert_eq!(sent.body.as_deref(), Some(&br#"{"status":"approved"}"#[..]));
+
+            let e = client
+                .decide_schedule_request("withdrawn", Decision::Approve)
+                .await
+                .unwrap_err();
+            assert_eq!(
+                e.api_errors().unwrap()[0].code(),
+                ErrorCode::RequestWithdrawn
+            );
+            assert_eq!(e.context().unwrap().endpoint, Endpoint::ScheduleRequest);
+        }
+    }
+
+    pub mod holiday {
+        use super::{ApprovalStep, Decision, Query, Status};
+        use crate::{endpoint, Client, EmployeeKey, Endpoint, Result};
+        use chrono::{DateTime, FixedOffset, NaiveDate};
+        use serde::{Deserialize, Serialize};
+
+        /// The holiday applications picked by `query`.
+        pub async fn list(access_token: &str, query: &Query) -> Result<Vec<HolidayRequest>> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .holiday_requests(query)
+                .await
+        }
+
+        /// Approves or rejects the holiday application `request_key`.
+        pub async fn decide(
+            access_token: &str,
+            request_key: &str,
+            decision: Decision,
+        ) -> Result<()> {
+            Client::builder()
+                .access_token(access_token)
+                .build()?
+                .decide_holiday_request(request_key, decision)
+                .await
+        }
+
+        impl Client {
+            /// The holiday applications picked by `query`. A `start` after
+            /// `end` fails with [`Error::Builder`](crate::Error::Builder)
+            /// without sending anything.
+            pub async fn holiday_requests(&self, query: &Query) -> Result<Vec<HolidayRequest>> {
+                let res = match query.query_string() {
+                    Ok(q) => self.get_with_query(endpoint::HOLIDAY_REQUESTS, &q).await,
+                    Err(e) => Err(e),
+                };
+                res.map_err(|e| e.with_context(query.context(Endpoint::HolidayRequests)))
+            }
+
+            /// Approves or rejects the holiday application `request_key`.
+            /// Approving more days than the employee has left fails with
+            /// [`ErrorCode::InsufficientHolidays`](crate::ErrorCode::InsufficientHolidays).
+            pub async fn decide_holiday_request(
+                &self,
+                request_key: &str,
+                decision: Decision,
+            ) -> Result<()> {
+                self.decide_request(
+                    &endpoint::holiday_request(request_key)?,
+                    Endpoint::HolidayRequest,
+                    &decision,
+                )
+                .await
             }
         }
 
-        impl Serialize for Code {
+        /// An application for taking a holiday, for days, half a day or
+        /// some hours.
+        #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct HolidayRequest {
+            pub request_key: String,
+            pub employee_key: EmployeeKey,
+            /// The code of the holiday type, e.g. 有休.
+            pub code: u32,
+            pub name: String,
+            /// The first day off.
+            pub start_date: NaiveDate,
+            /// The last day off, the same as `start_date` but for
+            /// [`HolidayUnit::FullDay`] holidays of several days.
+            pub end_date: NaiveDate,
+            pub unit: HolidayUnit,
+            /// Which half of the day, for [`HolidayUnit::HalfDay`]
+            /// holidays, e.g. `PM休`.
+            pub half_day_type_name: Option<String>,
+            /// When the holiday starts, for [`HolidayUnit::Hour`] holidays;
+            /// the offset the API sent is kept.
+            pub start: Option<DateTime<FixedOffset>>,
+            /// When the holiday ends, for [`HolidayUnit::Hour`] holidays.
+            pub end: Option<DateTime<FixedOffset>>,
+            pub status: Status,
+            #[serde(default)]
+            pub approval_route: Vec<ApprovalStep>,
+            /// What the applicant wrote.
+            pub comment: Option<String>,
+        }
+
+        /// How much of a day a [`HolidayRequest`] is for.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+        #[serde(from = "String")]
+        pub enum HolidayUnit {
+            FullDay,
+            HalfDay,
+            Hour,
+            /// A unit this version of the crate does not know about.
+            Unknown(String),
+        }
+
+        impl HolidayUnit {
+            /// The name of the unit in the API.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    HolidayUnit::FullDay => "fullDay",
+                    HolidayUnit::HalfDay => "halfDay",
+                    HolidayUnit::Hour => "hour",
+                    HolidayUnit::Unknown(unit) => unit,
+                }
+            }
+        }
+
+        impl From<String> for HolidayUnit {
+            fn from(unit: String) -> Self {
+                match &*unit {
+                    "fullDay" => HolidayUnit::FullDay,
+                    "halfDay" => HolidayUnit::HalfDay,
+                    "hour" => HolidayUnit::Hour,
+                    _ => HolidayUnit::Unknown(unit),
+                }
+            }
+        }
+
+        impl Serialize for HolidayUnit {
             fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
             where
                 S: serde::Serializer,
             {
-                match self {
-                    Code::In => serializer.serialize_str("1"),
-                    Code::Out => serializer.serialize_str("2"),
-                    Code::BreakStart => serializer.serialize_str("3"),
-                    Code::BreakEnd => serializer.serialize_str("4"),
-                }
+                serializer.serialize_str(self.as_str())
             }
         }
 
         #[test]
-        fn deserialize_response() {
+        fn deserialize_list() {
             let ex = r##"
+[
+  {
+    "requestKey": "5a6c0e2f7b5e4f8a9d1b3c4e6f708192",
+    "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+    "code": 1,
+    "name": "有休",
+    "startDate": "2016-05-02",
+    "endDate": "2016-05-06",
+    "unit": "fullDay",
+    "status": "approved",
+    "approvalRoute": [
+      {
+        "administratorKey": "4d2a8dbb",
+        "status": "approved"
+      }
+    ],
+    "comment": "帰省"
+  },
+  {
+    "requestKey": "0f1e2d3c4b5a69788796a5b4c3d2e1f0",
+    "employeeKey": "c0ffee3bd9b27e0deb9f294a03e3a1b0e5c8ea8fa8372ce72f1a3ca1c3f7e9d2",
+    "code": 1,
+    "name": "有休",
+    "startDate": "2016-05-09",
+    "endDate": "2016-05-09",
+    "unit": "halfDay",
+    "halfDayTypeName": "PM休",
+    "status": "applying"
+  },
+  {
+    "requestKey": "9a8b7c6d5e4f30211203f4e5d6c7b8a9",
+    "employeeKey": "c0ffee3bd9b27e0deb9f294a03e3a1b0e5c8ea8fa8372ce72f1a3ca1c3f7e9d2",
+    "code": 5,
+    "name": "時間有休",
+    "startDate": "2016-05-10",
+    "endDate": "2016-05-10",
+    "unit": "hour",
+    "start": "2016-05-10T10:00:00+09:00",
+    "end": "2016-05-10T12:00:00+09:00",
+    "status": "applying"
+  }
+]
+            "##;
+
+            let requests: Vec<HolidayRequest> = serde_json::from_str(ex).unwrap();
+            let full = &requests[0];
+            assert_eq!(full.unit, HolidayUnit::FullDay);
+            assert_eq!(full.end_date, NaiveDate::from_ymd_opt(2016, 5, 6).unwrap());
+            assert!(full.start.is_none());
+            assert_eq!(full.approval_route[0].status, Status::Approved);
+
+            let half = &requests[1];
+            assert_eq!(half.unit, HolidayUnit::HalfDay);
+            assert_eq!(half.half_day_type_name.as_deref(), Some("PM休"));
+            assert!(half.approval_route.is_empty());
+
+            let hourly = &requests[2];
+            assert_eq!(hourly.unit, HolidayUnit::Hour);
+            let start = hourly.start.unwrap();
+            assert_eq!(start.to_rfc3339(), "2016-05-10T10:00:00+09:00");
+            assert_eq!(start.offset().local_minus_utc(), 9 * 3600);
+            assert_eq!(
+                hourly.end.unwrap().to_rfc3339(),
+                "2016-05-10T12:00:00+09:00"
+            );
+        }
+
+        #[cfg(test)]
+        #[tokio::test]
+        async fn decide_through_transport() {
+            use crate::mock::{json, FakeTransport};
+            use crate::ErrorCode;
+
+            let transport = FakeTransport::new(|_| {
+                json(
+                    400,
+                    r#"{"errors": [{"message": "休暇の残日数が不足しています", "code": 305}]}"#,
+                )
+            });
+            let client = Client::with_transport(transport.clone(), "token");
+            let decision = Decision::Reject {
+                reason: Some("繁忙期のため".into()),
+            };
+            let e = client
+                .decide_holiday_request("5a6c0e2f", decision)
+                .await
+                .unwrap_err();
+            assert_eq!(
+                e.api_errors().unwrap()[0].code(),
+                ErrorCode::InsufficientHolidays
+            );
+
+            let sent = &transport.requests()[0];
+            assert_eq!(sent.method, http::Method::PUT);
+            assert_eq!(
+                sent.url.as_str(),
+                "https://api.kingtime.jp/v1.0/requests/holiday/5a6c0e2f"
+            );
+            let body: serde_json::Value =
+                serde_json::from_slice(sent.body.as_deref().unwrap()).unwrap();
+            assert_eq!(
+                body,
+                serde_json::json!({"status": "rejected", "comment": "繁忙期のため"})
+            );
+        }
+    }
+}
+
+pub mod yearly_holidays {
+    use super::{endpoint, Client, Endpoint, ErrorContext, Result};
+    use crate::employees::EmployeeKey;
+    use chrono::NaiveDate;
+    use futures::stream::{self, StreamExt, TryStreamExt};
+    use serde::{Deserialize, Serialize};
+
+    /// Number of employees [`Client::remaining_leave_summary`] asks about
+    /// at once.
+    const SUMMARY_CONCURRENCY: usize = 4;
+
+    /// The paid holidays granted to the employee `key`, and how much of
+    /// them is used.
+    pub async fn get(
+        access_token: &str,
+        key: &EmployeeKey,
+        query: &Query,
+    ) -> Result<YearlyHolidays> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .yearly_holidays(key, query)
+            .await
+    }
+
+    /// The paid holidays each of `keys` has left on `as_of`, in the order
+    /// of `keys`.
+    pub async fn remaining_summary(
+        access_token: &str,
+        keys: &[EmployeeKey],
+        as_of: NaiveDate,
+    ) -> Result<Vec<(EmployeeKey, RemainingLeave)>> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .remaining_leave_summary(keys, as_of)
+            .await
+    }
+
+    impl Client {
+        pub async fn yearly_holidays(
+            &self,
+            key: &EmployeeKey,
+            query: &Query,
+        ) -> Result<YearlyHolidays> {
+            self.get_with_query(&endpoint::yearly_holidays(key.as_str())?, query)
+                .await
+                .map_err(|e| {
+                    e.with_context(
+                        ErrorContext::new(Endpoint::YearlyHolidays).employee(key.as_str()),
+                    )
+                })
+        }
+
+        /// The paid holidays each of `keys` has left on `as_of`, in the
+        /// order of `keys`. Fails with the first employee whose holidays
+        /// can't be fetched.
+        pub async fn remaining_leave_summary(
+            &self,
+            keys: &[EmployeeKey],
+            as_of: NaiveDate,
+        ) -> Result<Vec<(EmployeeKey, RemainingLeave)>> {
+            let query = Query { date: Some(as_of) };
+            stream::iter(keys)
+                .map(|key| {
+                    let query = &query;
+                    async move {
+                        let holidays = self.yearly_holidays(key, query).await?;
+                        Ok((key.clone(), holidays.remaining_on(as_of)))
+                    }
+                })
+                .buffered(SUMMARY_CONCURRENCY)
+                .try_collect()
+                .await
+        }
+    }
+
+    /// Filters of [`get`].
+    #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+    pub struct Query {
+        /// The day the usage is counted up to, today if not set.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub date: Option<NaiveDate>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct YearlyHolidays {
+        pub employee_key: EmployeeKey,
+        /// Oldest first, expired ones included.
+        pub grants: Vec<Grant>,
+    }
+
+    /// Paid holidays granted at once. Holidays taken by the day and by the
+    /// hour are counted apart, as the API does: `used_days` doesn't include
+    /// `used_minutes`.
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Grant {
+        pub grant_date: NaiveDate,
+        pub expiry_date: NaiveDate,
+        pub granted_days: f64,
+        pub granted_minutes: u32,
+        pub used_days: f64,
+        pub used_minutes: u32,
+        pub remaining_days: f64,
+        pub remaining_minutes: u32,
+    }
+
+    impl Grant {
+        /// Whether the grant can't be used on `date` anymore. It still can
+        /// on its expiry date.
+        pub fn is_expired_on(&self, date: NaiveDate) -> bool {
+            self.expiry_date < date
+        }
+
+        /// Whether the grant can be used on `date`.
+        pub fn is_valid_on(&self, date: NaiveDate) -> bool {
+            self.grant_date <= date && !self.is_expired_on(date)
+        }
+    }
+
+    impl YearlyHolidays {
+        /// The sum of what is left of the grants valid on `date`.
+        pub fn remaining_on(&self, date: NaiveDate) -> RemainingLeave {
+            self.grants
+                .iter()
+                .filter(|grant| grant.is_valid_on(date))
+                .fold(RemainingLeave::default(), |sum, grant| RemainingLeave {
+                    days: sum.days + grant.remaining_days,
+                    minutes: sum.minutes + grant.remaining_minutes,
+                })
+        }
+    }
+
+    /// Paid holidays left, by the day and by the hour, see [`Grant`].
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct RemainingLeave {
+        pub days: f64,
+        pub minutes: u32,
+    }
+
+    #[test]
+    fn deserialize_response() {
+        let ex = r##"
+{
+  "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
+  "grants": [
+    {
+      "grantDate": "2014-04-01",
+      "expiryDate": "2016-03-31",
+      "grantedDays": 11,
+      "grantedMinutes": 0,
+      "usedDays": 8,
+      "usedMinutes": 120,
+      "remainingDays": 0,
+      "remainingMinutes": 0
+    },
+    {
+      "grantDate": "2015-04-01",
+      "expiryDate": "2017-03-31",
+      "grantedDays": 12,
+      "grantedMinutes": 0,
+      "usedDays": 4.5,
+      "usedMinutes": 180,
+      "remainingDays": 7,
+      "remainingMinutes": 300
+    },
+    {
+      "grantDate": "2016-04-01",
+      "expiryDate": "2018-03-31",
+      "grantedDays": 14,
+      "grantedMinutes": 0,
+      "usedDays": 0,
+      "usedMinutes": 0,
+      "remainingDays": 14,
+      "remainingMinutes": 0
+    }
+  ]
+}
+        "##;
+
+        let holidays: YearlyHolidays = serde_json::from_str(ex).unwrap();
+        let today = NaiveDate::from_ymd_opt(2016, 5, 1).unwrap();
+        let valid: Vec<_> = holidays
+            .grants
+            .iter()
+            .filter(|grant| !grant.is_expired_on(today))
+            .collect();
+        assert_eq!(valid.len(), 2);
+        assert!(holidays.grants[0].is_expired_on(today));
+        assert_eq!(valid[0].used_days, 4.5);
+        assert_eq!(valid[0].used_minutes, 180);
+        assert_eq!(valid[0].remaining_days, 7.0);
+        assert_eq!(valid[0].remaining_minutes, 300);
+        let remaining: f64 = valid.iter().map(|grant| grant.remaining_days).sum();
+        assert_eq!(remaining, 21.0);
+    }
+
+    #[test]
+    fn sum_remaining_leave() {
+        let grant = |grant_date: &str, expiry_date: &str, days, minutes| Grant {
+            grant_date: grant_date.parse().unwrap(),
+            expiry_date: expiry_date.parse().unwrap(),
+            granted_days: days,
+            granted_minutes: 0,
+            used_days: 0.0,
+            used_minutes: 0,
+            remaining_days: days,
+            remaining_minutes: minutes,
+        };
+        let holidays = YearlyHolidays {
+            employee_key: "a".into(),
+            grants: vec![
+                grant("2014-04-01", "2016-03-31", 2.5, 60),
+                grant("2015-04-01", "2017-03-31", 7.0, 300),
+                grant("2016-04-01", "2018-03-31", 14.0, 0),
+            ],
+        };
+        let on = |date: &str| holidays.remaining_on(date.parse().unwrap());
+
+        // the day a grant expires it is still there
+        assert_eq!(
+            on("2016-03-31"),
+            RemainingLeave {
+                days: 9.5,
+                minutes: 360
+            }
+        );
+        assert_eq!(
+            on("2016-04-01"),
+            RemainingLeave {
+                days: 21.0,
+                minutes: 300
+            }
+        );
+        assert_eq!(
+            on("2017-03-31"),
+            RemainingLeave {
+                days: 21.0,
+                minutes: 300
+            }
+        );
+        assert_eq!(
+            on("2017-04-01"),
+            RemainingLeave {
+                days: 14.0,
+                minutes: 0
+            }
+        );
+        assert_eq!(on("2020-01-01"), RemainingLeave::default());
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn summarize_employees() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|req| {
+            let key = req
+                .url
+                .path_segments()
+                .unwrap()
+                .next_back()
+                .unwrap()
+                .to_string();
+            let days = if key == "a" { 3 } else { 10 };
+            json(
+                200,
+                &format!(
+                    r#"{{"employeeKey": "{}", "grants": [{{"grantDate": "2016-04-01", "expiryDate": "2018-03-31", "grantedDays": {}, "grantedMinutes": 0, "usedDays": 0, "usedMinutes": 0, "remainingDays": {}, "remainingMinutes": 30}}]}}"#,
+                    key, days, days
+                ),
+            )
+        });
+        let client = Client::with_transport(transport.clone(), "token");
+        let as_of = NaiveDate::from_ymd_opt(2016, 5, 1).unwrap();
+        let summary = client
+            .remaining_leave_summary(&["a".into(), "b".into()], as_of)
+            .await
+            .unwrap();
+        assert_eq!(
+            summary,
             [
-                {
-                  "date": "2016-05-01",
-                  "dailyWorkings": [
-                    {
-                      "date": "2016-05-01",
-                      "employeeKey": "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3",
-                      "currentDateEmployee": {
-                        "divisionCode": "1000",
-                        "divisionName": "本社",
-                        "gender": "male",
-                        "typeCode": "1",
-                        "typeName": "正社員",
-                        "code": "1000",
-                        "lastName": "勤怠",
-                        "firstName": "太郎",
-                        "lastNamePhonetics": "キンタイ",
-                        "firstNamePhonetics": "タロウ",
-                        "employeeGroups": [
-                          {
-                            "code": "0001",
-                            "name": "人事部"
-                          },
-                          {
-                            "code": "0002",
-                            "name": "総務部"
-                          }
-                        ]
-                      },
-                      "timeRecord": [
-                        {
-                          "time": "2016-05-01T09:00:00+09:00",
-                          "code": "1",
-                          "name": "出勤",
-                          "divisionCode": "1000",
-                          "divisionName": "本社",
-                          "latitude": 35.6672237,
-                          "longitude": 139.7422207
-                        },
-                        {
-                          "time": "2015-05-01T18:00:00+09:00",
-                          "code": "2",
-                          "name": "退勤",
-                          "divisionCode": "1000",
-                          "divisionName": "本社",
-                          "credentialCode": 300,
-                          "credentialName": "KOTSL",
-                          "latitude": 35.6672237,
-                          "longitude": 139.7422207
-                        },
-                        {
-                          "time": "2016-05-01T10:00:00+09:00",
-                          "code": "3",
-                          "name": "休憩開始",
-                          "divisionCode": "1000",
-                          "divisionName": "本社"
-                        },
-                        {
-                          "time": "2016-05-01T11:00:00+09:00",
-                          "code": "4",
-                          "name": "休憩終了",
-                          "divisionCode": "1000",
-                          "divisionName": "本社"
-                        }
-                      ]
+                (
+                    "a".into(),
+                    RemainingLeave {
+                        days: 3.0,
+                        minutes: 30
                     }
-                  ]
-                }
-              ]
-            "##;
+                ),
+                (
+                    "b".into(),
+                    RemainingLeave {
+                        days: 10.0,
+                        minutes: 30
+                    }
+                ),
+            ]
+        );
+        assert!(transport
+            .requests()
+            .iter()
+            .all(|req| req.url.query() == Some("date=2016-05-01")));
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn get_through_transport() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|_| json(200, r#"{"employeeKey": "a", "grants": []}"#));
+        let client = Client::with_transport(transport.clone(), "token");
+        let query = Query {
+            date: NaiveDate::from_ymd_opt(2016, 5, 1),
+        };
+        let holidays = client.yearly_holidays(&"a".into(), &query).await.unwrap();
+        assert!(holidays.grants.is_empty());
+        assert_eq!(
+            transport.requests()[0].url.as_str(),
+            "https://api.kingtime.jp/v1.0/yearly-holidays/a?date=2016-05-01"
+        );
+    }
+}
+
+pub mod working_types {
+    use super::{endpoint, Client, Endpoint, ErrorContext, Result};
+    use serde::{Deserialize, Serialize};
+
+    /// Every working type (雇用区分) of the company. Their codes are the
+    /// `typeCode` of employees.
+    pub async fn list(access_token: &str) -> Result<Vec<WorkingType>> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .working_types()
+            .await
+    }
+
+    /// The working type `code`, e.g. the `typeCode` of an employee.
+    pub async fn get(access_token: &str, code: &str) -> Result<WorkingType> {
+        Client::builder()
+            .access_token(access_token)
+            .build()?
+            .working_type(code)
+            .await
+    }
 
-            let _: Response = serde_json::from_str(ex).unwrap();
+    impl Client {
+        pub async fn working_types(&self) -> Result<Vec<WorkingType>> {
+            self.get(endpoint::WORKING_TYPES)
+                .await
+                .map_err(|e| e.with_context(ErrorContext::new(Endpoint::WorkingTypes)))
         }
+
+        pub async fn working_type(&self, code: &str) -> Result<WorkingType> {
+            self.get(&endpoint::working_type(code)?)
+                .await
+                .map_err(|e| e.with_context(ErrorContext::new(Endpoint::WorkingType)))
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    pub struct WorkingType {
+        pub code: String,
+        pub name: String,
+    }
+
+    #[test]
+    fn deserialize_list() {
+        let ex = r##"
+[
+  {
+    "code": "1",
+    "name": "正社員",
+    "sortOrder": 1
+  },
+  {
+    "code": "2",
+    "name": "アルバイト",
+    "closingDay": 20,
+    "sortOrder": 2
+  }
+]
+        "##;
+
+        let types: Vec<WorkingType> = serde_json::from_str(ex).unwrap();
+        assert_eq!(
+            types,
+            [
+                WorkingType {
+                    code: "1".to_string(),
+                    name: "正社員".to_string(),
+                },
+                WorkingType {
+                    code: "2".to_string(),
+                    name: "アルバイト".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[cfg(test)]
+    #[tokio::test]
+    async fn get_working_type() {
+        use crate::mock::{json, FakeTransport};
+
+        let transport = FakeTransport::new(|req| match req.url.path() {
+            "/v1.0/working-types/1" => json(200, r#"{"code": "1", "name": "正社員"}"#),
+            _ => json(
+                404,
+                r#"{"errors": [{"message": "対象が存在しません", "code": 302}]}"#,
+            ),
+        });
+        let client = Client::with_transport(transport.clone(), "token");
+
+        let working_type = client.working_type("1").await.unwrap();
+        assert_eq!(working_type.name, "正社員");
+        let e = client.working_type("パート/1").await.unwrap_err();
+        assert!(e.is_not_found(), "{:?}", e);
+        assert_eq!(
+            transport.requests()[1].url.as_str(),
+            "https://api.kingtime.jp/v1.0/working-types/%E3%83%91%E3%83%BC%E3%83%88%2F1"
+        );
     }
 }
@@ -0,0 +1,138 @@
+use crate::endpoint::Endpoint;
+use crate::ErrorKind;
+use http::{Method, StatusCode};
+use std::time::Duration;
+
+/// What became of one API call, as passed to a [`MetricsSink`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RequestMetrics {
+    pub endpoint: Endpoint,
+    pub method: Method,
+    /// Status of the last response, if any arrived.
+    pub status: Option<StatusCode>,
+    /// Time taken by the whole call, retries included.
+    pub elapsed: Duration,
+    pub retries: u32,
+    /// `None` if the call succeeded.
+    pub error: Option<ErrorKind>,
+}
+
+/// Receives the [`RequestMetrics`] of every call made through a client set
+/// up with [`Client::with_metrics`](crate::Client::with_metrics).
+///
+/// Implemented for closures, so counting calls can be as short as:
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// let calls = Arc::new(AtomicUsize::new(0));
+/// let counter = calls.clone();
+/// let client = kingtime::Client::new("token").with_metrics(move |_: &kingtime::RequestMetrics| {
+///     counter.fetch_add(1, Ordering::Relaxed);
+/// });
+/// ```
+pub trait MetricsSink: Send + Sync + 'static {
+    /// Called once per call, after it succeeded or failed.
+    fn record(&self, metrics: &RequestMetrics);
+}
+
+/// The sink clients start with, discarding everything.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetrics;
+
+impl MetricsSink for NoopMetrics {
+    fn record(&self, _: &RequestMetrics) {}
+}
+
+impl<F> MetricsSink for F
+where
+    F: Fn(&RequestMetrics) + Send + Sync + 'static,
+{
+    fn record(&self, metrics: &RequestMetrics) {
+        self(metrics)
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn record_each_call() {
+    use crate::mock::{json, FakeTransport};
+    use crate::{Client, RetryPolicy};
+    use std::sync::{Arc, Mutex};
+
+    let transport = FakeTransport::new(|req| match req.url.path() {
+        "/v1.0/employees/1000" => json(
+            200,
+            r#"{"code": "1000", "key": "8b6ee646", "lastName": "勤怠", "firstName": "太郎"}"#,
+        ),
+        "/v1.0/daily-workings" => json(503, "<html>Service Unavailable</html>"),
+        _ => json(
+            404,
+            r#"{"errors": [{"message": "対象が存在しません", "code": 302}]}"#,
+        ),
+    });
+    let recorded = Arc::new(Mutex::new(vec![]));
+    let sink = || {
+        let recorded = recorded.clone();
+        move |metrics: &RequestMetrics| recorded.lock().unwrap().push(metrics.clone())
+    };
+    let client = Client::builder()
+        .access_token("token")
+        .transport(transport.clone())
+        .retry(RetryPolicy::new(3).base_delay(Duration::from_millis(1)))
+        .build()
+        .unwrap()
+        .with_metrics(sink());
+
+    client.employee(&"1000".into()).await.unwrap();
+    client.daily_workings().await.unwrap_err();
+    client.employee(&"2000".into()).await.unwrap_err();
+    let client = Client::builder()
+        .token_provider(|| async { Err::<String, _>("vault is sealed") })
+        .transport(transport)
+        .build()
+        .unwrap()
+        .with_metrics(sink());
+    client.employee(&"1000".into()).await.unwrap_err();
+
+    let recorded = recorded.lock().unwrap();
+    let summary: Vec<_> = recorded
+        .iter()
+        .map(|m| (m.endpoint, m.method.clone(), m.status, m.retries, m.error))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                Endpoint::Employee,
+                Method::GET,
+                Some(StatusCode::OK),
+                0,
+                None
+            ),
+            (
+                Endpoint::DailyWorkings,
+                Method::GET,
+                Some(StatusCode::SERVICE_UNAVAILABLE),
+                2,
+                Some(ErrorKind::Api)
+            ),
+            (
+                Endpoint::Employee,
+                Method::GET,
+                Some(StatusCode::NOT_FOUND),
+                0,
+                Some(ErrorKind::Api)
+            ),
+            (
+                Endpoint::Employee,
+                Method::GET,
+                None,
+                0,
+                Some(ErrorKind::Other)
+            ),
+        ]
+    );
+}
@@ -0,0 +1,243 @@
+// Test doubles: a tiny HTTP/1.1 server for exercising the client against real
+// sockets, and an in-memory transport for skipping the network altogether.
+#![allow(dead_code)]
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::transport::{HttpRequest, HttpResponse, Transport};
+use futures::future::BoxFuture;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| &**v)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub delay: Option<Duration>,
+    pub hang_up: bool,
+}
+
+impl Response {
+    pub fn json(status: u16, body: &str) -> Self {
+        Response {
+            status,
+            headers: vec![("Content-Type".into(), "application/json".into())],
+            body: body.as_bytes().to_vec(),
+            delay: None,
+            hang_up: false,
+        }
+    }
+
+    // closes the connection once the request is read, as if the response got
+    // lost on the way back
+    pub fn hang_up() -> Self {
+        Response {
+            hang_up: true,
+            ..Response::json(200, "")
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+type Handler = dyn Fn(&Request) -> Response + Send + Sync;
+
+struct State {
+    connections: AtomicUsize,
+    requests: Mutex<Vec<Request>>,
+    handler: Box<Handler>,
+}
+
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<State>,
+}
+
+impl MockServer {
+    pub async fn start(handler: impl Fn(&Request) -> Response + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(State {
+            connections: AtomicUsize::new(0),
+            requests: Mutex::new(vec![]),
+            handler: Box::new(handler),
+        });
+        let accept_state = state.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                accept_state.connections.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(serve(stream, accept_state.clone()));
+            }
+        });
+        MockServer { addr, state }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}{}", self.addr, path)
+    }
+
+    pub fn connections(&self) -> usize {
+        self.state.connections.load(Ordering::SeqCst)
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.requests.lock().unwrap().clone()
+    }
+}
+
+async fn serve(stream: TcpStream, state: Arc<State>) {
+    let mut stream = BufReader::new(stream);
+    while let Some(req) = read_request(&mut stream).await {
+        state.requests.lock().unwrap().push(req.clone());
+        let resp = (state.handler)(&req);
+        if let Some(delay) = resp.delay {
+            tokio::time::sleep(delay).await;
+        }
+        if resp.hang_up {
+            return;
+        }
+        let mut out = format!(
+            "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\n",
+            resp.status,
+            resp.body.len()
+        );
+        for (k, v) in &resp.headers {
+            out.push_str(&format!("{}: {}\r\n", k, v));
+        }
+        out.push_str("\r\n");
+        let mut out = out.into_bytes();
+        out.extend_from_slice(&resp.body);
+        if stream.get_mut().write_all(&out).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn read_request(stream: &mut BufReader<TcpStream>) -> Option<Request> {
+    let mut line = String::new();
+    if stream.read_line(&mut line).await.ok()? == 0 {
+        return None;
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?.to_string();
+
+    let mut headers = vec![];
+    loop {
+        let mut line = String::new();
+        stream.read_line(&mut line).await.ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (k, v) = line.split_once(':')?;
+        headers.push((k.trim().to_string(), v.trim().to_string()));
+    }
+
+    let len = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, v)| v.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; len];
+    stream.read_exact(&mut body).await.ok()?;
+
+    Some(Request {
+        method,
+        target,
+        headers,
+        body,
+    })
+}
+
+type TransportHandler = dyn Fn(&HttpRequest) -> HttpResponse + Send + Sync;
+
+// An in-memory `Transport` answering every request with `handler`.
+#[derive(Clone)]
+pub struct FakeTransport {
+    handler: Arc<TransportHandler>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+    delay: Option<Duration>,
+    in_flight: Arc<AtomicUsize>,
+    max_in_flight: Arc<AtomicUsize>,
+}
+
+impl FakeTransport {
+    pub fn new(handler: impl Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static) -> Self {
+        FakeTransport {
+            handler: Arc::new(handler),
+            requests: Arc::default(),
+            delay: None,
+            in_flight: Arc::default(),
+            max_in_flight: Arc::default(),
+        }
+    }
+
+    // makes requests overlap so that concurrency can be observed
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    // the most requests that were ever being answered at the same time
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight.load(Ordering::SeqCst)
+    }
+}
+
+impl Transport for FakeTransport {
+    fn send(&self, req: HttpRequest) -> BoxFuture<'_, crate::Result<HttpResponse>> {
+        let resp = (self.handler)(&req);
+        self.requests.lock().unwrap().push(req);
+        Box::pin(async move {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(resp)
+        })
+    }
+}
+
+pub fn json(status: u16, body: &str) -> HttpResponse {
+    HttpResponse {
+        status: http::StatusCode::from_u16(status).unwrap(),
+        headers: Default::default(),
+        body: body.as_bytes().to_vec(),
+    }
+}
@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use http::header::{self, HeaderMap};
+use std::time::Duration;
+
+/// Request quota reported by the server in the `X-RateLimit-*` headers.
+///
+/// Each field is `None` when the corresponding header was absent or malformed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    /// When the quota is replenished, as sent by the server.
+    pub reset: Option<u64>,
+}
+
+impl RateLimitInfo {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let get = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse().ok())
+        };
+        let info = RateLimitInfo {
+            limit: get("x-ratelimit-limit"),
+            remaining: get("x-ratelimit-remaining"),
+            reset: get("x-ratelimit-reset"),
+        };
+        if info == RateLimitInfo::default() {
+            None
+        } else {
+            Some(info)
+        }
+    }
+}
+
+// `Retry-After` is either a number of seconds or an HTTP date
+pub(crate) fn retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = headers.get(header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[test]
+fn parse_retry_after() {
+    let now = "2016-05-01T00:00:00Z".parse().unwrap();
+    let mut headers = HeaderMap::new();
+    assert_eq!(retry_after(&headers, now), None);
+
+    headers.insert(header::RETRY_AFTER, "120".parse().unwrap());
+    assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(120)));
+
+    headers.insert(
+        header::RETRY_AFTER,
+        "Sun, 01 May 2016 00:00:30 GMT".parse().unwrap(),
+    );
+    assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(30)));
+
+    headers.insert(
+        header::RETRY_AFTER,
+        "Sat, 30 Apr 2016 23:00:00 GMT".parse().unwrap(),
+    );
+    assert_eq!(retry_after(&headers, now), Some(Duration::from_secs(0)));
+
+    headers.insert(header::RETRY_AFTER, "later".parse().unwrap());
+    assert_eq!(retry_after(&headers, now), None);
+}
+
+#[test]
+fn parse_headers() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(RateLimitInfo::from_headers(&headers), None);
+
+    headers.insert("X-RateLimit-Limit", "100".parse().unwrap());
+    headers.insert("X-RateLimit-Remaining", " 42".parse().unwrap());
+    headers.insert("X-RateLimit-Reset", "soon".parse().unwrap());
+    assert_eq!(
+        RateLimitInfo::from_headers(&headers),
+        Some(RateLimitInfo {
+            limit: Some(100),
+            remaining: Some(42),
+            reset: None,
+        })
+    );
+}
@@ -0,0 +1,194 @@
+//! Calls to endpoints this crate doesn't cover yet, answered with the JSON
+//! as returned.
+//!
+//! They go through the same client as the typed API, so the base URL,
+//! headers, retries, rate limiting and error mapping all apply:
+//!
+//! ```no_run
+//! # async fn run() -> kingtime::Result<()> {
+//! let token = std::env::var("KOT_TOKEN").unwrap_or_default();
+//! let tokens = kingtime::raw::get(&token, "tokens/valid", &[]).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::endpoint::Endpoint;
+use crate::{Client, Error, ErrorContext, Result};
+use http::Method;
+use serde_json::Value;
+use url::Url;
+
+/// Gets `path`, relative to the versioned API root like `employees/1000`,
+/// with `query` as its query string. Paths leaving the root, e.g. through
+/// `..`, or carrying a query of their own fail with [`Error::Builder`].
+pub async fn get(access_token: &str, path: &str, query: &[(&str, &str)]) -> Result<Value> {
+    Client::builder()
+        .access_token(access_token)
+        .build()?
+        .raw_get(path, query)
+        .await
+}
+
+/// Sends `body` to `path` with `method`, see [`get`].
+pub async fn send(
+    access_token: &str,
+    method: Method,
+    path: &str,
+    query: &[(&str, &str)],
+    body: Option<&Value>,
+) -> Result<Value> {
+    Client::builder()
+        .access_token(access_token)
+        .build()?
+        .raw_send(method, path, query, body)
+        .await
+}
+
+impl Client {
+    /// Gets `path` on the API, see [`raw::get`](get).
+    pub async fn raw_get(&self, path: &str, query: &[(&str, &str)]) -> Result<Value> {
+        self.raw_send(Method::GET, path, query, None).await
+    }
+
+    /// Sends `body` to `path` on the API, see [`raw::send`](send).
+    pub async fn raw_send(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<&Value>,
+    ) -> Result<Value> {
+        // The base URL is to be left only through the typed API. URL
+        // resolution also steps up on dot segments, escaped or not, and takes
+        // backslashes for slashes.
+        let dot_segment = |segment: &str| {
+            matches!(
+                &*segment.to_ascii_lowercase().replace("%2e", "."),
+                "." | ".."
+            )
+        };
+        if path.starts_with('/')
+            || Url::parse(path).is_ok()
+            || path.contains('\\')
+            || path.split('/').any(dot_segment)
+        {
+            return Err(Error::Builder(format!(
+                "path {:?} is not relative to the API root",
+                path
+            )));
+        }
+        // The query has an argument of its own.
+        if path.contains(['?', '#']) {
+            return Err(Error::Builder(format!(
+                "path {:?} has a query or fragment",
+                path
+            )));
+        }
+        let query = Some(query).filter(|query| !query.is_empty());
+        self.request(method, path, query, body)
+            .await
+            .map_err(|e| e.with_context(ErrorContext::new(Endpoint::of(path))))
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn send_raw_requests() {
+    use crate::mock::{json, FakeTransport};
+    use serde_json::json;
+
+    let transport = FakeTransport::new(|req| match req.url.path() {
+        "/v1.0/tokens/valid" => json(200, r#"{"valid": true}"#),
+        "/v1.0/daily-workings/timerecord/c0ffee" => json(201, ""),
+        _ => json(
+            404,
+            r#"{"errors": [{"message": "対象が存在しません", "code": 302}]}"#,
+        ),
+    });
+    let client = Client::with_transport(transport.clone(), "token");
+
+    let token = client
+        .raw_get("tokens/valid", &[("token", "token")])
+        .await
+        .unwrap();
+    assert_eq!(token, json!({"valid": true}));
+    let body = json!({"date": "2016-05-01", "code": "1"});
+    let resp = client
+        .raw_send(
+            Method::POST,
+            "daily-workings/timerecord/c0ffee",
+            &[],
+            Some(&body),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp, Value::Null);
+
+    let requests = transport.requests();
+    assert_eq!(
+        requests[0].url.as_str(),
+        "https://api.kingtime.jp/v1.0/tokens/valid?token=token"
+    );
+    assert_eq!(requests[1].method, Method::POST);
+    assert_eq!(
+        requests[1].url.as_str(),
+        "https://api.kingtime.jp/v1.0/daily-workings/timerecord/c0ffee"
+    );
+    assert_eq!(
+        serde_json::from_slice::<Value>(requests[1].body.as_deref().unwrap()).unwrap(),
+        body
+    );
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn map_raw_errors() {
+    use crate::mock::{json, FakeTransport};
+    use crate::ErrorData;
+
+    let transport = FakeTransport::new(|req| match req.url.path() {
+        "/v1.0/employees/1000" => json(
+            200,
+            r#"{"errors": [{"message": "パラメータが不正です", "code": 200}]}"#,
+        ),
+        _ => json(
+            404,
+            r#"{"errors": [{"message": "対象が存在しません", "code": 302}]}"#,
+        ),
+    });
+    let client = Client::with_transport(transport.clone(), "token");
+
+    let e = client.raw_get("employees/1000", &[]).await.unwrap_err();
+    assert_eq!(
+        e.api_errors().unwrap(),
+        [ErrorData {
+            message: "パラメータが不正です".to_string(),
+            code: 200,
+        }]
+    );
+    assert!(
+        e.to_string().starts_with("employees/{employeeCode}: "),
+        "{}",
+        e
+    );
+    let e = client.raw_get("employees/2000", &[]).await.unwrap_err();
+    assert!(e.is_not_found(), "{:?}", e);
+    assert_eq!(transport.requests().len(), 2);
+
+    for path in &[
+        "/employees",
+        "https://example.com/v1.0/employees",
+        "../v2.0/employees",
+        "employees/../../x",
+        "employees/./1000",
+        "employees/%2E%2e/x",
+        "employees\\..\\..\\x",
+        "..",
+        "employees?division=1000",
+        "employees#1000",
+    ] {
+        let e = client.raw_get(path, &[]).await.unwrap_err();
+        assert!(matches!(e, Error::Builder(_)), "{:?}", e);
+    }
+    assert_eq!(transport.requests().len(), 2);
+}
@@ -0,0 +1,221 @@
+use crate::Error;
+use http::{Method, StatusCode};
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// An unsuccessful attempt, as seen by [`RetryPolicy::retry_if`].
+#[derive(Debug)]
+pub enum Failure<'a> {
+    /// The server answered with a non-success status.
+    Status(StatusCode),
+    /// The request could not be completed at all.
+    Error(&'a Error),
+}
+
+impl Failure<'_> {
+    /// Connection failures, connections lost mid-request, timeouts, 5xx and
+    /// 429.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Failure::Status(status) => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            Failure::Error(Error::Timeout(_)) => true,
+            Failure::Error(Error::RateLimited { .. }) => true,
+            Failure::Error(e) => e.is_connect() || e.is_connection_lost(),
+        }
+    }
+
+    /// Whether the request provably never reached the server, because no
+    /// connection could be established. Only then are POST and PUT requests
+    /// retried by default.
+    pub fn is_unsent(&self) -> bool {
+        match self {
+            Failure::Error(e) => e.is_connect(),
+            Failure::Status(_) => false,
+        }
+    }
+}
+
+type Predicate = dyn Fn(&Failure) -> bool + Send + Sync;
+
+/// How failed requests are retried.
+///
+/// GET, HEAD and DELETE requests are retried on any failure the policy
+/// accepts. Re-sending a POST or PUT may record the same punch twice, so
+/// those are only retried when they [never reached the server], unless
+/// [`RetryPolicy::retry_non_idempotent`] or
+/// [`RequestOptions::retry_non_idempotent`] is set.
+///
+/// [never reached the server]: Failure::is_unsent
+/// [`RequestOptions::retry_non_idempotent`]: crate::RequestOptions::retry_non_idempotent
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    non_idempotent: bool,
+    max_retry_after: Duration,
+    retry_if: Arc<Predicate>,
+}
+
+impl RetryPolicy {
+    /// Makes at most `max_attempts` attempts in total, including the first one.
+    pub fn new(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            non_idempotent: false,
+            max_retry_after: Duration::from_secs(60),
+            retry_if: Arc::new(|failure: &Failure| failure.is_transient()),
+        }
+    }
+
+    /// Delay before the first retry, doubled on every subsequent one.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Randomizes each delay between half and all of its nominal value.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Retries POST and PUT requests like any other, for every call.
+    pub fn retry_non_idempotent(mut self, enabled: bool) -> Self {
+        self.non_idempotent = enabled;
+        self
+    }
+
+    /// Longest `Retry-After` of a 429 response the client is willing to wait
+    /// for before retrying, 60 seconds by default. Longer waits fail with
+    /// [`Error::RateLimited`] right away.
+    pub fn max_retry_after(mut self, max: Duration) -> Self {
+        self.max_retry_after = max;
+        self
+    }
+
+    /// Replaces the default [`Failure::is_transient`] check.
+    pub fn retry_if(
+        mut self,
+        predicate: impl Fn(&Failure) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.retry_if = Arc::new(predicate);
+        self
+    }
+
+    pub(crate) fn applies_to(&self, method: &Method) -> bool {
+        self.non_idempotent || matches!(*method, Method::GET | Method::HEAD | Method::DELETE)
+    }
+
+    // `attempt` is the number of attempts made so far.
+    pub(crate) fn should_retry(&self, attempt: u32, failure: &Failure) -> bool {
+        attempt < self.max_attempts && (self.retry_if)(failure)
+    }
+
+    // The server's `Retry-After` takes precedence over the backoff schedule.
+    pub(crate) fn delay_for(
+        &self,
+        attempt: u32,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        match retry_after {
+            Some(wait) if wait > self.max_retry_after => None,
+            Some(wait) => Some(wait),
+            None => Some(self.delay(attempt)),
+        }
+    }
+
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .checked_mul(1 << (attempt - 1).min(16))
+            .unwrap_or(self.max_delay);
+        let delay = exp.min(self.max_delay);
+        if self.jitter {
+            delay / 2 + delay.mul_f64(random() / 2.0)
+        } else {
+            delay
+        }
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .field("jitter", &self.jitter)
+            .field("non_idempotent", &self.non_idempotent)
+            .field("max_retry_after", &self.max_retry_after)
+            .finish()
+    }
+}
+
+// uniformly distributed in [0, 1); good enough for spreading out retries
+fn random() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[test]
+fn exponential_delay() {
+    let policy = RetryPolicy::new(10)
+        .base_delay(Duration::from_millis(100))
+        .max_delay(Duration::from_secs(1))
+        .jitter(false);
+    assert_eq!(policy.delay(1), Duration::from_millis(100));
+    assert_eq!(policy.delay(2), Duration::from_millis(200));
+    assert_eq!(policy.delay(3), Duration::from_millis(400));
+    assert_eq!(policy.delay(5), Duration::from_secs(1));
+    assert_eq!(policy.delay(100), Duration::from_secs(1));
+
+    let policy = policy.jitter(true);
+    for attempt in 1..5 {
+        let delay = policy.delay(attempt);
+        let nominal = Duration::from_millis(100 << (attempt - 1));
+        assert!(nominal / 2 <= delay && delay <= nominal, "{:?}", delay);
+    }
+}
+
+#[test]
+fn honor_retry_after() {
+    let policy = RetryPolicy::new(3)
+        .base_delay(Duration::from_millis(100))
+        .max_retry_after(Duration::from_secs(10))
+        .jitter(false);
+    assert_eq!(policy.delay_for(1, None), Some(Duration::from_millis(100)));
+    assert_eq!(
+        policy.delay_for(1, Some(Duration::from_secs(5))),
+        Some(Duration::from_secs(5))
+    );
+    assert_eq!(policy.delay_for(1, Some(Duration::from_secs(11))), None);
+}
+
+#[test]
+fn transient_failures() {
+    assert!(Failure::Status(StatusCode::BAD_GATEWAY).is_transient());
+    assert!(Failure::Status(StatusCode::SERVICE_UNAVAILABLE).is_transient());
+    assert!(Failure::Status(StatusCode::TOO_MANY_REQUESTS).is_transient());
+    assert!(!Failure::Status(StatusCode::UNAUTHORIZED).is_transient());
+    assert!(!Failure::Status(StatusCode::NOT_FOUND).is_transient());
+    assert!(!Failure::Error(&Error::Api {
+        status: StatusCode::BAD_REQUEST,
+        errors: vec![],
+    })
+    .is_transient());
+}
@@ -0,0 +1,302 @@
+//! A fake KoT API for testing code built on this crate, behind the
+//! `test-util` feature.
+//!
+//! [`MockKot`] is a [`Transport`] answering from an in-memory company, so no
+//! server or network is involved:
+//!
+//! ```
+//! # async fn run() -> kingtime::Result<()> {
+//! use kingtime::test_util::MockKot;
+//!
+//! let mock = MockKot::new().with_employee("2000", "c0ffee");
+//! let client = mock.client();
+//! assert_eq!(client.employee(&"2000".into()).await?.key, "c0ffee");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::daily_workings::timerecord::{Code, TimeRecord};
+use crate::transport::{BoxFuture, HttpRequest, HttpResponse, Transport};
+use crate::{Client, EmployeeCode, EmployeeKey, Result};
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use futures::future;
+use http::{Method, StatusCode};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// Code of the employee [`MockKot::new`] starts with.
+pub const EMPLOYEE_CODE: &str = "1000";
+/// Key of the employee [`MockKot::new`] starts with.
+pub const EMPLOYEE_KEY: &str = "8b6ee646a9620b286499c3df6918c4888a97dd7bbc6a26a18743f4697a1de4b3";
+
+/// A time record received by [`MockKot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostedRecord {
+    pub employee_key: EmployeeKey,
+    pub date: NaiveDate,
+    /// The time as posted, with its offset.
+    pub time: DateTime<FixedOffset>,
+    pub code: Code,
+}
+
+#[derive(Debug)]
+struct Employee {
+    key: EmployeeKey,
+    last_name: String,
+    first_name: String,
+}
+
+// punches of each employee, by key
+type Day = BTreeMap<EmployeeKey, Vec<TimeRecord>>;
+
+#[derive(Debug, Default)]
+struct State {
+    // by code
+    employees: BTreeMap<EmployeeCode, Employee>,
+    records: BTreeMap<NaiveDate, Day>,
+    posted: Vec<PostedRecord>,
+}
+
+/// An in-memory KoT company serving employees, daily workings and time
+/// records. Posted time records are kept, and show up in later reads.
+///
+/// Clones share the same company.
+#[derive(Debug, Clone, Default)]
+pub struct MockKot {
+    state: Arc<Mutex<State>>,
+}
+
+impl MockKot {
+    /// A company with one employee, [`EMPLOYEE_CODE`] 勤怠 太郎, who worked
+    /// from 9:00 to 18:00 on 2016-05-01.
+    pub fn new() -> Self {
+        let date = NaiveDate::from_ymd_opt(2016, 5, 1).unwrap();
+        let at = |time: &str| time.parse().unwrap();
+        MockKot::empty()
+            .with_named_employee(EMPLOYEE_CODE, EMPLOYEE_KEY, "勤怠", "太郎")
+            .with_timerecords(
+                EMPLOYEE_KEY,
+                date,
+                vec![
+                    TimeRecord::new(at("2016-05-01T09:00:00+09:00"), Code::In),
+                    TimeRecord::new(at("2016-05-01T18:00:00+09:00"), Code::Out),
+                ],
+            )
+    }
+
+    /// A company without any employees.
+    pub fn empty() -> Self {
+        MockKot::default()
+    }
+
+    pub fn with_employee(self, code: impl Into<EmployeeCode>, key: impl Into<EmployeeKey>) -> Self {
+        self.with_named_employee(code, key, "勤怠", "花子")
+    }
+
+    pub fn with_named_employee(
+        self,
+        code: impl Into<EmployeeCode>,
+        key: impl Into<EmployeeKey>,
+        last_name: &str,
+        first_name: &str,
+    ) -> Self {
+        self.state.lock().unwrap().employees.insert(
+            code.into(),
+            Employee {
+                key: key.into(),
+                last_name: last_name.to_string(),
+                first_name: first_name.to_string(),
+            },
+        );
+        self
+    }
+
+    /// Adds `records` to what the employee `key` punched on `date`.
+    pub fn with_timerecords(
+        self,
+        key: impl Into<EmployeeKey>,
+        date: NaiveDate,
+        records: impl IntoIterator<Item = TimeRecord>,
+    ) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .records
+            .entry(date)
+            .or_default()
+            .entry(key.into())
+            .or_default()
+            .extend(records);
+        self
+    }
+
+    /// A client talking to this mock.
+    pub fn client(&self) -> Client {
+        Client::with_transport(self.clone(), "mock-token")
+    }
+
+    /// Every time record posted so far, in order.
+    pub fn posted_records(&self) -> Vec<PostedRecord> {
+        self.state.lock().unwrap().posted.clone()
+    }
+
+    fn handle(&self, req: &HttpRequest) -> (StatusCode, Value) {
+        let segments: Vec<_> = req
+            .url
+            .path_segments()
+            .map(|segments| segments.collect())
+            .unwrap_or_default();
+        // skip the API version
+        let segments = segments.get(1..).unwrap_or_default();
+        let mut state = self.state.lock().unwrap();
+        match (&req.method, segments) {
+            (&Method::GET, ["employees"]) => {
+                let employees = state.employees.iter().map(|(code, employee)| {
+                    json!({
+                        "code": code,
+                        "key": employee.key,
+                        "lastName": employee.last_name,
+                        "firstName": employee.first_name,
+                    })
+                });
+                (StatusCode::OK, Value::Array(employees.collect()))
+            }
+            (&Method::GET, ["employees", code]) => match state.employees.get(*code) {
+                Some(employee) => (
+                    StatusCode::OK,
+                    json!({
+                        "code": code,
+                        "key": employee.key,
+                        "lastName": employee.last_name,
+                        "firstName": employee.first_name,
+                    }),
+                ),
+                None => not_found(),
+            },
+            (&Method::GET, ["daily-workings"]) => {
+                let days = state.records.iter().map(|(date, employees)| {
+                    let workings: Vec<_> = employees
+                        .keys()
+                        .map(|key| json!({"date": date, "employeeKey": key}))
+                        .collect();
+                    json!({"date": date, "dailyWorkings": workings})
+                });
+                (StatusCode::OK, Value::Array(days.collect()))
+            }
+            (&Method::GET, ["daily-workings", "timerecord"]) => {
+                let param = |name: &str| {
+                    req.url
+                        .query_pairs()
+                        .find(|(k, _)| k == name)
+                        .map(|(_, v)| v.into_owned())
+                };
+                // everyone unless given
+                let keys = param("employeeKeys");
+                let keys: Option<Vec<_>> = keys.as_deref().map(|keys| keys.split(',').collect());
+                let date = |name| param(name).and_then(|date| date.parse::<NaiveDate>().ok());
+                let (start, end) = match (date("start"), date("end")) {
+                    (Some(start), Some(end)) => (start, end),
+                    _ => return invalid_parameter(),
+                };
+                let days = state.records.range(start..=end).map(|(date, employees)| {
+                    let workings: Vec<_> = employees
+                        .iter()
+                        .filter(|(key, _)| match &keys {
+                            Some(keys) => keys.contains(&key.as_str()),
+                            None => true,
+                        })
+                        .map(|(key, records)| {
+                            let records: Vec<_> = records
+                                .iter()
+                                .map(|record| {
+                                    let mut json =
+                                        json!({"time": jst(&record.time), "code": record.code});
+                                    if let Some(code) = record.credential_code {
+                                        json["credentialCode"] = code.into();
+                                    }
+                                    if let Some(name) = &record.credential_name {
+                                        json["credentialName"] = name.as_str().into();
+                                    }
+                                    json
+                                })
+                                .collect();
+                            json!({"date": date, "employeeKey": key, "timeRecord": records})
+                        })
+                        .collect();
+                    json!({"date": date, "dailyWorkings": workings})
+                });
+                (StatusCode::OK, Value::Array(days.collect()))
+            }
+            (&Method::POST, ["daily-workings", "timerecord", key]) => {
+                #[derive(Deserialize)]
+                struct Posted {
+                    date: NaiveDate,
+                    time: DateTime<FixedOffset>,
+                    code: Code,
+                    #[serde(rename = "credentialCode")]
+                    credential_code: Option<u32>,
+                }
+
+                let body = req.body.as_deref().unwrap_or_default();
+                let posted: Posted = match serde_json::from_slice(body) {
+                    Ok(posted) => posted,
+                    Err(_) => return invalid_parameter(),
+                };
+                if !state.employees.values().any(|e| e.key == **key) {
+                    return not_found();
+                }
+                state
+                    .records
+                    .entry(posted.date)
+                    .or_default()
+                    .entry(EmployeeKey::from(*key))
+                    .or_default()
+                    .push(TimeRecord {
+                        credential_code: posted.credential_code,
+                        ..TimeRecord::new(posted.time, posted.code.clone())
+                    });
+                state.posted.push(PostedRecord {
+                    employee_key: EmployeeKey::from(*key),
+                    date: posted.date,
+                    time: posted.time,
+                    code: posted.code,
+                });
+                (StatusCode::CREATED, json!({}))
+            }
+            _ => not_found(),
+        }
+    }
+}
+
+impl Transport for MockKot {
+    fn send(&self, req: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        let (status, body) = self.handle(&req);
+        Box::pin(future::ready(Ok(HttpResponse {
+            status,
+            headers: Default::default(),
+            body: body.to_string().into_bytes(),
+        })))
+    }
+}
+
+fn jst(time: &DateTime<FixedOffset>) -> String {
+    let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+    time.with_timezone(&jst).to_rfc3339()
+}
+
+fn not_found() -> (StatusCode, Value) {
+    error(StatusCode::NOT_FOUND, 302, "対象が存在しません")
+}
+
+fn invalid_parameter() -> (StatusCode, Value) {
+    error(StatusCode::BAD_REQUEST, 200, "パラメータが不正です")
+}
+
+fn error(status: StatusCode, code: u32, message: &str) -> (StatusCode, Value) {
+    (
+        status,
+        json!({"errors": [{"message": message, "code": code}]}),
+    )
+}
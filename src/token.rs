@@ -0,0 +1,131 @@
+use crate::transport::BoxFuture;
+use futures::future;
+use std::fmt;
+use std::future::Future;
+
+/// The error type of a failed [`AccessTokenProvider`].
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// An access token that stays out of `Debug` and `Display` output.
+///
+/// With the `zeroize` feature its buffer is wiped when dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretToken(String);
+
+impl SecretToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        SecretToken(token.into())
+    }
+
+    /// The token itself, only to be sent to the API.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    // for showing request bodies, which may well contain the token
+    pub(crate) fn redact(&self, body: &[u8]) -> String {
+        String::from_utf8_lossy(body).replace(&self.0, "[REDACTED]")
+    }
+}
+
+impl From<String> for SecretToken {
+    fn from(token: String) -> Self {
+        SecretToken(token)
+    }
+}
+
+impl From<&str> for SecretToken {
+    fn from(token: &str) -> Self {
+        SecretToken(token.to_string())
+    }
+}
+
+impl fmt::Debug for SecretToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl fmt::Display for SecretToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretToken {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+/// Supplies the access token of each request a [`Client`](crate::Client)
+/// makes.
+///
+/// Implemented for `String` and `&'static str`, which always hand out the
+/// same token, and for closures returning a future, which can fetch it from
+/// a secret store. Caching is up to the provider: it is asked again before
+/// every request.
+///
+/// ```no_run
+/// # async fn fetch_from_vault() -> Result<String, std::io::Error> { todo!() }
+/// let client = kingtime::Client::builder()
+///     .token_provider(|| fetch_from_vault())
+///     .build()?;
+/// # Ok::<(), kingtime::Error>(())
+/// ```
+pub trait AccessTokenProvider: Send + Sync + 'static {
+    fn token(&self) -> BoxFuture<'_, Result<String, BoxError>>;
+}
+
+impl AccessTokenProvider for SecretToken {
+    fn token(&self) -> BoxFuture<'_, Result<String, BoxError>> {
+        Box::pin(future::ready(Ok(self.0.clone())))
+    }
+}
+
+impl AccessTokenProvider for String {
+    fn token(&self) -> BoxFuture<'_, Result<String, BoxError>> {
+        Box::pin(future::ready(Ok(self.clone())))
+    }
+}
+
+impl AccessTokenProvider for &'static str {
+    fn token(&self) -> BoxFuture<'_, Result<String, BoxError>> {
+        Box::pin(future::ready(Ok(self.to_string())))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<F, Fut, E> AccessTokenProvider for F
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<String, E>> + Send + 'static,
+    E: Into<BoxError>,
+{
+    fn token(&self) -> BoxFuture<'_, Result<String, BoxError>> {
+        let fut = self();
+        Box::pin(async move { fut.await.map_err(Into::into) })
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<F, Fut, E> AccessTokenProvider for F
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<String, E>> + 'static,
+    E: Into<BoxError>,
+{
+    fn token(&self) -> BoxFuture<'_, Result<String, BoxError>> {
+        let fut = self();
+        Box::pin(async move { fut.await.map_err(Into::into) })
+    }
+}
+
+#[test]
+fn redact_token() {
+    let token = SecretToken::from("secret-token");
+    assert_eq!(format!("{:?}", token), "[REDACTED]");
+    assert_eq!(token.to_string(), "[REDACTED]");
+    assert_eq!(token.expose(), "secret-token");
+}
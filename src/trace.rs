@@ -0,0 +1,186 @@
+// Instrumentation behind the `tracing` feature. Without it every hook here is
+// an empty inline function, so nothing is left in the compiled client.
+use crate::endpoint::Endpoint;
+use crate::token::SecretToken;
+use crate::transport::HttpRequest;
+use crate::Result;
+use http::StatusCode;
+use std::future::Future;
+use std::time::Duration;
+
+pub(crate) use imp::*;
+
+#[cfg(feature = "tracing")]
+mod imp {
+    use super::*;
+    use tracing::field::Empty;
+    use tracing::Instrument;
+
+    pub(crate) use tracing::Span;
+
+    pub(crate) fn request(
+        req: &HttpRequest,
+        access_token: &SecretToken,
+        endpoint: Endpoint,
+    ) -> Span {
+        // a key about to be replaced is as sensitive as the token
+        let path = if endpoint.hides_path() {
+            endpoint.as_str()
+        } else {
+            req.url.path()
+        };
+        let span = tracing::info_span!(
+            "kingtime",
+            method = %req.method,
+            path,
+            status = Empty,
+            elapsed_ms = Empty,
+        );
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            if let Some(body) = &req.body {
+                let body = access_token.redact(body);
+                span.in_scope(|| tracing::debug!(body = %body, "request body"));
+            }
+        }
+        span
+    }
+
+    pub(crate) fn instrument<F: Future>(span: Span, future: F) -> impl Future<Output = F::Output> {
+        future.instrument(span)
+    }
+
+    pub(crate) fn status(span: &Span, status: StatusCode) {
+        span.record("status", status.as_u16());
+    }
+
+    pub(crate) fn finish<T>(span: &Span, res: &Result<T>, elapsed: Duration) {
+        span.record("elapsed_ms", elapsed.as_millis() as u64);
+        if let Err(e) = res {
+            tracing::warn!(error = %e, "request failed");
+        }
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod imp {
+    use super::*;
+
+    #[derive(Clone)]
+    pub(crate) struct Span;
+
+    #[inline(always)]
+    pub(crate) fn request(_: &HttpRequest, _: &SecretToken, _: Endpoint) -> Span {
+        Span
+    }
+
+    #[inline(always)]
+    pub(crate) fn instrument<F: Future>(_: Span, future: F) -> F {
+        future
+    }
+
+    #[inline(always)]
+    pub(crate) fn status(_: &Span, _: StatusCode) {}
+
+    #[inline(always)]
+    pub(crate) fn finish<T>(_: &Span, _: &Result<T>, _: Duration) {}
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn record_spans() {
+    use crate::mock::{MockServer, Response};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    // collects every span name, event and recorded field as a line of text
+    #[derive(Clone, Default)]
+    struct Collect(Arc<Mutex<Vec<String>>>);
+
+    struct Fields<'a>(&'a mut String);
+
+    impl Visit for Fields<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.push_str(&format!(" {}={:?}", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Collect {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+        fn new_span(&self, span: &Attributes) -> Id {
+            let mut line = format!("span {}", span.metadata().name());
+            span.record(&mut Fields(&mut line));
+            self.0.lock().unwrap().push(line);
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, values: &Record) {
+            let mut line = "record".to_string();
+            values.record(&mut Fields(&mut line));
+            self.0.lock().unwrap().push(line);
+        }
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event) {
+            let mut line = format!("event {}", event.metadata().level());
+            event.record(&mut Fields(&mut line));
+            self.0.lock().unwrap().push(line);
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    let collect = Collect::default();
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    tracing::subscriber::with_default(collect.clone(), || {
+        rt.block_on(async {
+            let server = MockServer::start(|_| {
+                Response::json(400, r#"{"errors": [{"message": "bad", "code": 1}]}"#)
+            })
+            .await;
+            let client = crate::Client::builder()
+                .access_token("secret-token")
+                .base_url(server.url("/"))
+                .build()
+                .unwrap();
+            let res: Result<serde_json::Value> = client
+                .post("daily-workings/timerecord/x", &["secret-token"])
+                .await;
+            assert!(res.is_err());
+            assert!(client
+                .reissue_employee_key(&"old-key".into())
+                .await
+                .is_err());
+        })
+    });
+
+    let lines = collect.0.lock().unwrap().join("\n");
+    assert!(
+        lines.contains("span kingtime method=POST path=\"/v1.0/daily-workings/timerecord/x\""),
+        "{}",
+        lines
+    );
+    assert!(lines.contains("record status=400"), "{}", lines);
+    assert!(lines.contains("record elapsed_ms="), "{}", lines);
+    assert!(
+        lines.contains("event DEBUG message=request body body=[\"[REDACTED]\"]"),
+        "{}",
+        lines
+    );
+    assert!(
+        lines.contains("event WARN message=request failed error="),
+        "{}",
+        lines
+    );
+    assert!(
+        lines.contains("span kingtime method=PUT path=\"employees/{employeeKey}/key\""),
+        "{}",
+        lines
+    );
+    assert!(!lines.contains("secret-token"), "{}", lines);
+    assert!(!lines.contains("old-key"), "{}", lines);
+}
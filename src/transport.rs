@@ -0,0 +1,180 @@
+use crate::token::SecretToken;
+use crate::Result;
+use http::header::{self, HeaderMap};
+use http::{Method, StatusCode};
+use std::fmt;
+use std::time::Duration;
+use url::Url;
+
+/// A fully prepared API request: the URL already carries the query string and
+/// the headers include authorization.
+///
+/// Its `Debug` output shows the `Authorization` header as
+/// `Bearer [REDACTED]`, and any other sensitive header as `[REDACTED]`.
+#[derive(Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+    pub timeout: Option<Duration>,
+}
+
+impl fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // the body may well repeat the token sent in the header
+        let access_token = self
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .filter(|access_token| !access_token.is_empty())
+            .map(SecretToken::from);
+        let body = self.body.as_deref().map(|body| match &access_token {
+            Some(access_token) => access_token.redact(body),
+            None => String::from_utf8_lossy(body).into_owned(),
+        });
+        f.debug_struct("HttpRequest")
+            .field("method", &self.method)
+            .field("url", &self.url.as_str())
+            .field("headers", &RedactedHeaders(&self.headers))
+            .field("body", &body)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+struct RedactedHeaders<'a>(&'a HeaderMap);
+
+impl fmt::Debug for RedactedHeaders<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut map = f.debug_map();
+        for (name, value) in self.0 {
+            if name == header::AUTHORIZATION {
+                map.entry(name, &"Bearer [REDACTED]");
+            } else if value.is_sensitive() {
+                map.entry(name, &"[REDACTED]");
+            } else {
+                map.entry(name, value);
+            }
+        }
+        map.finish()
+    }
+}
+
+/// The future returned by [`Transport::send`]. It is `Send` except on wasm,
+/// where the browser's fetch API can't be driven from another thread anyway.
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxFuture<'a, T> = futures::future::BoxFuture<'a, T>;
+#[cfg(target_arch = "wasm32")]
+pub type BoxFuture<'a, T> = futures::future::LocalBoxFuture<'a, T>;
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+/// The HTTP stack a [`Client`](crate::Client) sends its requests through.
+///
+/// [`ReqwestTransport`] is used unless another one is given to
+/// [`Client::with_transport`](crate::Client::with_transport), which is
+/// mostly useful for feeding canned responses to code under test.
+pub trait Transport: Send + Sync + 'static {
+    fn send(&self, req: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>>;
+}
+
+/// The default [`Transport`], built from the [`ClientBuilder`] settings
+/// unless given its own `reqwest::Client`. Requires a tokio runtime except on
+/// wasm.
+///
+/// [`ClientBuilder`]: crate::ClientBuilder
+#[cfg(feature = "reqwest")]
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl Transport for ReqwestTransport {
+    fn send(&self, req: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        Box::pin(async move {
+            let mut builder = self
+                .client
+                .request(req.method, req.url)
+                .headers(req.headers);
+            if let Some(body) = req.body {
+                builder = builder.body(body);
+            }
+            // not supported by fetch
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(timeout) = req.timeout {
+                builder = builder.timeout(timeout);
+            }
+            let resp = builder.send().await?;
+            Ok(HttpResponse {
+                status: resp.status(),
+                headers: resp.headers().clone(),
+                body: resp.bytes().await?.to_vec(),
+            })
+        })
+    }
+}
+
+/// A [`Transport`] on top of isahc and libcurl, which runs on any executor
+/// (async-std, smol, `futures::executor`...) rather than requiring tokio.
+///
+/// It is the default when the `isahc` feature is enabled without
+/// `reqwest`, i.e. with default features off.
+#[cfg(all(feature = "isahc", not(target_arch = "wasm32")))]
+#[derive(Debug, Clone)]
+pub struct IsahcTransport {
+    client: isahc::HttpClient,
+}
+
+#[cfg(all(feature = "isahc", not(target_arch = "wasm32")))]
+impl IsahcTransport {
+    pub fn new(client: isahc::HttpClient) -> Self {
+        IsahcTransport { client }
+    }
+}
+
+#[cfg(all(feature = "isahc", not(target_arch = "wasm32")))]
+impl Transport for IsahcTransport {
+    fn send(&self, req: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        use isahc::config::Configurable;
+        use isahc::AsyncReadResponseExt;
+
+        Box::pin(async move {
+            let mut builder = isahc::Request::builder()
+                .method(req.method)
+                .uri(req.url.as_str());
+            if let Some(timeout) = req.timeout {
+                builder = builder.timeout(timeout);
+            }
+            let body = match req.body {
+                Some(body) => isahc::AsyncBody::from(body),
+                None => isahc::AsyncBody::empty(),
+            };
+            let mut request = builder
+                .body(body)
+                .map_err(|e| crate::Error::Builder(format!("invalid request: {}", e)))?;
+            *request.headers_mut() = req.headers;
+            let mut resp = self.client.send_async(request).await?;
+            let body = resp.bytes().await.map_err(isahc::Error::from)?;
+            Ok(HttpResponse {
+                status: resp.status(),
+                headers: resp.headers().clone(),
+                body,
+            })
+        })
+    }
+}
@@ -0,0 +1,386 @@
+//! Types shared by the responses of several endpoints.
+
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+/// What the result of a custom working item counts, its
+/// `calculationUnitCode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "u32")]
+pub enum CalculationUnit {
+    Days,
+    Times,
+    Minutes,
+    /// A code this crate doesn't know of.
+    Unknown(u32),
+}
+
+impl CalculationUnit {
+    /// The code of the unit in the API.
+    pub fn as_code(&self) -> u32 {
+        match self {
+            CalculationUnit::Days => 1,
+            CalculationUnit::Times => 2,
+            CalculationUnit::Minutes => 4,
+            CalculationUnit::Unknown(code) => *code,
+        }
+    }
+}
+
+impl Serialize for CalculationUnit {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u32(self.as_code())
+    }
+}
+
+impl fmt::Display for CalculationUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalculationUnit::Days => f.write_str("days"),
+            CalculationUnit::Times => f.write_str("times"),
+            CalculationUnit::Minutes => f.write_str("minutes"),
+            CalculationUnit::Unknown(code) => write!(f, "unit {}", code),
+        }
+    }
+}
+
+impl From<u32> for CalculationUnit {
+    fn from(code: u32) -> Self {
+        match code {
+            1 => CalculationUnit::Days,
+            2 => CalculationUnit::Times,
+            4 => CalculationUnit::Minutes,
+            code => CalculationUnit::Unknown(code),
+        }
+    }
+}
+
+/// An employee as they were on the day or month of a working record, its
+/// `currentDateEmployee`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentDateEmployee {
+    pub code: EmployeeCode,
+    pub last_name: String,
+    pub first_name: String,
+    pub last_name_phonetics: Option<String>,
+    pub first_name_phonetics: Option<String>,
+    pub division_code: Option<String>,
+    pub division_name: Option<String>,
+    pub gender: Option<Gender>,
+    pub type_code: Option<String>,
+    pub type_name: Option<String>,
+    #[serde(default)]
+    pub employee_groups: Vec<EmployeeGroup>,
+}
+
+/// The gender of an employee, written `male` or `female` by the API but read
+/// in any case.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "String")]
+pub enum Gender {
+    Male,
+    Female,
+    /// A value this crate doesn't know of, as sent.
+    Other(String),
+}
+
+impl Gender {
+    /// The name of the gender in the API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Gender::Male => "male",
+            Gender::Female => "female",
+            Gender::Other(gender) => gender,
+        }
+    }
+}
+
+impl From<String> for Gender {
+    fn from(gender: String) -> Self {
+        if gender.eq_ignore_ascii_case("male") {
+            Gender::Male
+        } else if gender.eq_ignore_ascii_case("female") {
+            Gender::Female
+        } else {
+            Gender::Other(gender)
+        }
+    }
+}
+
+impl Serialize for Gender {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// A duration counted in minutes, as the API gives working hours. It is
+/// written as the bare number.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize, Serialize,
+)]
+#[serde(transparent)]
+pub struct Minutes(pub u32);
+
+impl Minutes {
+    pub fn as_minutes(&self) -> u32 {
+        self.0
+    }
+
+    pub fn as_duration(&self) -> chrono::Duration {
+        chrono::Duration::minutes(self.0.into())
+    }
+}
+
+impl From<u32> for Minutes {
+    fn from(minutes: u32) -> Self {
+        Minutes(minutes)
+    }
+}
+
+impl Add for Minutes {
+    type Output = Minutes;
+
+    fn add(self, other: Minutes) -> Minutes {
+        Minutes(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Minutes {
+    fn add_assign(&mut self, other: Minutes) {
+        self.0 += other.0;
+    }
+}
+
+impl Sum for Minutes {
+    fn sum<I: Iterator<Item = Minutes>>(iter: I) -> Self {
+        iter.fold(Minutes(0), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Minutes> for Minutes {
+    fn sum<I: Iterator<Item = &'a Minutes>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Written like `10h 15m`, or `45m` under an hour.
+impl fmt::Display for Minutes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.0 / 60, self.0 % 60) {
+            (0, minutes) => write!(f, "{}m", minutes),
+            (hours, minutes) => write!(f, "{}h {}m", hours, minutes),
+        }
+    }
+}
+
+macro_rules! id {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                $name(id)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                $name(id.to_string())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+id! {
+    /// The key identifying an employee across the API, as found in
+    /// `employeeKey` fields. It is generated by KING OF TIME, unlike
+    /// [`EmployeeCode`].
+    EmployeeKey
+}
+
+id! {
+    /// The code of an employee chosen by the company, such as a staff
+    /// number. Only [`employees::get`](crate::employees::get) looks employees
+    /// up by it.
+    EmployeeCode
+}
+
+/// A group an employee belongs to, besides their division.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct EmployeeGroup {
+    pub code: String,
+    pub name: String,
+}
+
+#[test]
+fn map_calculation_units() {
+    for &(code, unit, name) in &[
+        (1, CalculationUnit::Days, "days"),
+        (2, CalculationUnit::Times, "times"),
+        (4, CalculationUnit::Minutes, "minutes"),
+        (3, CalculationUnit::Unknown(3), "unit 3"),
+        (99, CalculationUnit::Unknown(99), "unit 99"),
+    ] {
+        assert_eq!(CalculationUnit::from(code), unit);
+        assert_eq!(unit.as_code(), code);
+        assert_eq!(unit.to_string(), name);
+        assert_eq!(
+            serde_json::from_str::<CalculationUnit>(&code.to_string()).unwrap(),
+            unit
+        );
+    }
+}
+
+#[test]
+fn bucket_by_group() {
+    use std::collections::HashMap;
+
+    let ex = r##"
+[
+  [{"code": "0001", "name": "人事部"}, {"code": "0002", "name": "総務部"}],
+  [{"code": "0002", "name": "総務部"}],
+  []
+]
+    "##;
+
+    let groups: Vec<Vec<EmployeeGroup>> = serde_json::from_str(ex).unwrap();
+    let mut members = HashMap::<_, Vec<_>>::new();
+    for (employee, groups) in groups.iter().enumerate() {
+        for group in groups {
+            members.entry(group).or_default().push(employee);
+        }
+    }
+    let general_affairs = EmployeeGroup {
+        code: "0002".into(),
+        name: "総務部".into(),
+    };
+    assert_eq!(members.len(), 2);
+    assert_eq!(members[&general_affairs], [0, 1]);
+}
+
+#[test]
+fn round_trip_genders() {
+    for (json, gender, back) in &[
+        (r#""male""#, Gender::Male, r#""male""#),
+        (r#""female""#, Gender::Female, r#""female""#),
+        (r#""Male""#, Gender::Male, r#""male""#),
+        (r#""FEMALE""#, Gender::Female, r#""female""#),
+    ] {
+        assert_eq!(serde_json::from_str::<Gender>(json).unwrap(), *gender);
+        assert_eq!(serde_json::to_string(gender).unwrap(), *back);
+    }
+
+    let other: Gender = serde_json::from_str(r#""unspecified""#).unwrap();
+    assert_eq!(other, Gender::Other("unspecified".into()));
+    assert_eq!(serde_json::to_string(&other).unwrap(), r#""unspecified""#);
+}
+
+#[test]
+fn read_ids_transparently() {
+    use serde_json::json;
+
+    let key: EmployeeKey = serde_json::from_str(r#""8b6ee646""#).unwrap();
+    assert_eq!(key, EmployeeKey::from("8b6ee646"));
+    assert_eq!(key, "8b6ee646");
+    assert_eq!(key.to_string(), "8b6ee646");
+    assert_eq!(serde_json::to_value(&key).unwrap(), json!("8b6ee646"));
+
+    let employee: CurrentDateEmployee = serde_json::from_value(json!({
+        "code": "1000",
+        "lastName": "勤怠",
+        "firstName": "太郎",
+    }))
+    .unwrap();
+    assert_eq!(employee.code, EmployeeCode::from("1000".to_string()));
+    assert_eq!(employee.code.as_ref(), "1000");
+    assert_eq!(
+        serde_json::to_value(&employee).unwrap()["code"],
+        json!("1000")
+    );
+    assert!(serde_json::from_value::<EmployeeCode>(json!(1000)).is_err());
+}
+
+#[test]
+fn format_minutes() {
+    for &(minutes, formatted) in &[
+        (0, "0m"),
+        (45, "45m"),
+        (60, "1h 0m"),
+        (615, "10h 15m"),
+        (10920, "182h 0m"),
+    ] {
+        assert_eq!(Minutes(minutes).to_string(), formatted);
+    }
+}
+
+#[test]
+fn add_minutes() {
+    let breaks = [Minutes(45), Minutes(15)];
+    assert_eq!(breaks.iter().sum::<Minutes>(), Minutes(60));
+    assert_eq!(breaks.iter().copied().sum::<Minutes>(), Minutes(60));
+    let mut total = Minutes(480) + Minutes(135);
+    total += Minutes(5);
+    assert_eq!(total.as_minutes(), 620);
+    assert_eq!(total.as_duration(), chrono::Duration::minutes(620));
+    assert_eq!(
+        serde_json::from_str::<Minutes>("615").unwrap(),
+        Minutes(615)
+    );
+    assert_eq!(serde_json::to_string(&Minutes(615)).unwrap(), "615");
+}
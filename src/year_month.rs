@@ -0,0 +1,134 @@
+use chrono::{Datelike, NaiveDate};
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A month of a year, written `YYYY-MM` by the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct YearMonth {
+    year: i32,
+    month: u32,
+}
+
+impl YearMonth {
+    /// `None` unless `month` is in `1..=12` and the year is one chrono
+    /// supports.
+    pub fn new(year: i32, month: u32) -> Option<Self> {
+        NaiveDate::from_ymd_opt(year, month, 1).map(YearMonth::of)
+    }
+
+    /// The month `date` is in.
+    pub fn of(date: NaiveDate) -> Self {
+        YearMonth {
+            year: date.year(),
+            month: date.month(),
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    pub fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1).unwrap()
+    }
+
+    pub fn last_day(&self) -> NaiveDate {
+        match self.succ_opt() {
+            Some(next) => next.first_day().pred_opt().unwrap(),
+            None => NaiveDate::MAX,
+        }
+    }
+
+    /// The next month, `None` past the last year chrono supports.
+    pub fn succ_opt(&self) -> Option<Self> {
+        match self.month {
+            12 => YearMonth::new(self.year + 1, 1),
+            month => YearMonth::new(self.year, month + 1),
+        }
+    }
+
+    /// The previous month, `None` before the first year chrono supports.
+    pub fn pred_opt(&self) -> Option<Self> {
+        match self.month {
+            1 => YearMonth::new(self.year - 1, 12),
+            month => YearMonth::new(self.year, month - 1),
+        }
+    }
+}
+
+impl fmt::Display for YearMonth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+impl FromStr for YearMonth {
+    type Err = chrono::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NaiveDate::parse_from_str(&format!("{}-01", s), "%Y-%m-%d").map(YearMonth::of)
+    }
+}
+
+impl Serialize for YearMonth {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for YearMonth {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(&s), &"a YYYY-MM month"))
+    }
+}
+
+#[test]
+fn format_and_parse() {
+    let month = YearMonth::new(2016, 5).unwrap();
+    assert_eq!(month.to_string(), "2016-05");
+    assert_eq!("2016-05".parse::<YearMonth>().unwrap(), month);
+    assert_eq!(
+        month.first_day(),
+        NaiveDate::from_ymd_opt(2016, 5, 1).unwrap()
+    );
+    assert_eq!(
+        month.last_day(),
+        NaiveDate::from_ymd_opt(2016, 5, 31).unwrap()
+    );
+    assert_eq!(
+        YearMonth::new(2016, 12).unwrap().last_day(),
+        NaiveDate::from_ymd_opt(2016, 12, 31).unwrap()
+    );
+    assert_eq!(
+        YearMonth::new(2016, 12).unwrap().succ_opt(),
+        YearMonth::new(2017, 1)
+    );
+    assert_eq!(
+        YearMonth::new(2017, 1).unwrap().pred_opt(),
+        YearMonth::new(2016, 12)
+    );
+    assert!(YearMonth::new(2016, 13).is_none());
+    assert!("2016-13".parse::<YearMonth>().is_err());
+
+    assert_eq!(serde_json::to_string(&month).unwrap(), r#""2016-05""#);
+    assert_eq!(
+        serde_json::from_str::<YearMonth>(r#""2016-05""#).unwrap(),
+        month
+    );
+    assert!(serde_json::from_str::<YearMonth>(r#""2016-05-01""#).is_err());
+}
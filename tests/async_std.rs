@@ -0,0 +1,133 @@
+// The client driven by async-std through `IsahcTransport`, with no tokio
+// runtime around.
+use async_std::io::prelude::*;
+use async_std::io::BufReader;
+use async_std::net::{TcpListener, TcpStream};
+use async_std::task;
+use kingtime::{Client, ErrorKind, IsahcTransport, RetryPolicy};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const EMPLOYEE: &str =
+    r#"{"code": "1000", "key": "8b6ee646", "lastName": "勤怠", "firstName": "太郎"}"#;
+
+// Answers each request with what `handler` returns for its request line,
+// after waiting for the given delay.
+async fn serve(
+    handler: impl Fn(&str) -> (u16, &'static str, Duration) + Send + Sync + 'static,
+) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let handler = Arc::new(handler);
+    task::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            task::spawn(connection(stream, handler.clone()));
+        }
+    });
+    url
+}
+
+async fn connection(
+    stream: TcpStream,
+    handler: Arc<impl Fn(&str) -> (u16, &'static str, Duration)>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.clone());
+    let mut stream = stream;
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).await? == 0 {
+            return Ok(());
+        }
+        let mut len = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).await?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    len = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; len];
+        reader.read_exact(&mut body).await?;
+
+        let (status, body, delay) = handler(request_line.trim_end());
+        task::sleep(delay).await;
+        let resp = format!(
+            "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        stream.write_all(resp.as_bytes()).await?;
+    }
+}
+
+fn client(base_url: &str) -> Client {
+    let transport = IsahcTransport::new(isahc::HttpClient::new().unwrap());
+    Client::builder()
+        .access_token("token")
+        .base_url(base_url)
+        .transport(transport)
+        .timeout(Duration::from_millis(500))
+        .retry(RetryPolicy::new(3).base_delay(Duration::from_millis(10)))
+        .build()
+        .unwrap()
+}
+
+#[async_std::test]
+async fn get_employee() {
+    let url = serve(|line| match line {
+        "GET /v1.0/employees/1000 HTTP/1.1" => (200, EMPLOYEE, Duration::ZERO),
+        _ => (
+            404,
+            r#"{"errors": [{"message": "", "code": 302}]}"#,
+            Duration::ZERO,
+        ),
+    })
+    .await;
+    let client = client(&url);
+
+    let employee = client.employee(&"1000".into()).await.unwrap();
+    assert_eq!(employee.key, "8b6ee646");
+    assert!(client
+        .employee(&"2000".into())
+        .await
+        .unwrap_err()
+        .is_not_found());
+}
+
+#[async_std::test]
+async fn retry_server_errors() {
+    let attempts = AtomicUsize::new(0);
+    let url = serve(move |_| {
+        if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+            (503, "", Duration::ZERO)
+        } else {
+            (200, EMPLOYEE, Duration::ZERO)
+        }
+    })
+    .await;
+
+    let employee = client(&url).employee(&"1000".into()).await.unwrap();
+    assert_eq!(employee.key, "8b6ee646");
+}
+
+#[async_std::test]
+async fn time_out() {
+    let url = serve(|_| (200, EMPLOYEE, Duration::from_secs(5))).await;
+
+    let res = client(&url)
+        .with_options(kingtime::RequestOptions {
+            timeout: Some(Duration::from_millis(100)),
+            ..Default::default()
+        })
+        .employee(&"1000".into())
+        .await;
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::Timeout);
+}
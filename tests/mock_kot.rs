@@ -0,0 +1,138 @@
+use chrono::NaiveDate;
+use kingtime::daily_workings::timerecord::{Code, Request, TimeRecord};
+use kingtime::test_util::{MockKot, PostedRecord, EMPLOYEE_CODE, EMPLOYEE_KEY};
+
+fn date(s: &str) -> NaiveDate {
+    s.parse().unwrap()
+}
+
+#[tokio::test]
+async fn preloaded_company() {
+    let client = MockKot::new().client();
+
+    let employee = client.employee(&EMPLOYEE_CODE.into()).await.unwrap();
+    assert_eq!(employee.key, EMPLOYEE_KEY);
+    assert_eq!(employee.last_name, "勤怠");
+    let employees = client.employees(&Default::default()).await.unwrap();
+    let keys: Vec<_> = employees.iter().map(|e| e.key.as_str()).collect();
+    assert_eq!(keys, [EMPLOYEE_KEY]);
+
+    let day = date("2016-05-01");
+    let resp = client
+        .timerecords(&[EMPLOYEE_KEY.into()], day, day)
+        .await
+        .unwrap();
+    let records = &resp.0[0].daily_workings[0].time_record;
+    let codes: Vec<_> = records.iter().map(|record| &record.code).collect();
+    assert_eq!(codes, [&Code::In, &Code::Out]);
+
+    let resp = client.daily_workings().await.unwrap();
+    assert_eq!(resp.0[0].daily_workings[0].employee_key, EMPLOYEE_KEY);
+
+    let res = client.employee(&"9999".into()).await;
+    assert!(res.unwrap_err().is_not_found());
+}
+
+#[tokio::test]
+async fn record_posted_timerecords() {
+    let mock = MockKot::empty()
+        .with_employee("2000", "c0ffee")
+        .with_timerecords(
+            "c0ffee",
+            date("2016-05-02"),
+            vec![TimeRecord::new(
+                "2016-05-02T09:30:00+09:00".parse().unwrap(),
+                Code::In,
+            )],
+        );
+    let client = mock.client();
+
+    let req = Request::new(
+        date("2016-05-02"),
+        "2016-05-02T18:30:00+09:00".parse().unwrap(),
+        Code::Out,
+    );
+    client
+        .post_timerecord(&"c0ffee".into(), &req)
+        .await
+        .unwrap();
+    assert!(client
+        .post_timerecord(&"unknown".into(), &req)
+        .await
+        .unwrap_err()
+        .is_not_found());
+
+    assert_eq!(
+        mock.posted_records(),
+        [PostedRecord {
+            employee_key: "c0ffee".into(),
+            date: req.date,
+            time: req.time.into(),
+            code: Code::Out,
+        }]
+    );
+    assert_eq!(
+        mock.posted_records()[0].time.to_rfc3339(),
+        "2016-05-02T18:30:00+09:00"
+    );
+
+    let day = date("2016-05-02");
+    let resp = client
+        .timerecords(&["c0ffee".into()], day, day)
+        .await
+        .unwrap();
+    let codes: Vec<_> = resp.0[0].daily_workings[0]
+        .time_record
+        .iter()
+        .map(|record| &record.code)
+        .collect();
+    assert_eq!(codes, [&Code::In, &Code::Out]);
+}
+
+#[tokio::test]
+async fn read_back_credential() {
+    let mock = MockKot::new();
+    let client = mock.client();
+
+    let day = date("2016-05-02");
+    let req = Request {
+        credential_code: Some(300),
+        ..Request::new(day, "2016-05-02T09:00:00+09:00".parse().unwrap(), Code::In)
+    };
+    client
+        .post_timerecord(&EMPLOYEE_KEY.into(), &req)
+        .await
+        .unwrap();
+
+    let resp = client
+        .timerecords(&[EMPLOYEE_KEY.into()], day, day)
+        .await
+        .unwrap();
+    let record = &resp.0[0].daily_workings[0].time_record[0];
+    assert_eq!(record.credential_code, Some(300));
+}
+
+#[tokio::test]
+async fn timerecords_of_everyone() {
+    let mock = MockKot::new().with_timerecords(
+        "c0ffee",
+        date("2016-05-01"),
+        vec![TimeRecord::new(
+            "2016-05-01T10:00:00+09:00".parse().unwrap(),
+            Code::In,
+        )],
+    );
+    let client = mock.with_employee("2000", "c0ffee").client();
+
+    let day = date("2016-05-01");
+    let resp = client
+        .all_timerecords(day, day, &Default::default())
+        .await
+        .unwrap();
+    let keys: Vec<_> = resp.0[0]
+        .daily_workings
+        .iter()
+        .map(|working| working.employee_key.as_str())
+        .collect();
+    assert_eq!(keys, [EMPLOYEE_KEY, "c0ffee"]);
+}